@@ -0,0 +1,260 @@
+use cryptopals_crypto_challenges::set_1_basics::c3_single_byte_xor_cypher::decipher_bytes;
+use cryptopals_crypto_challenges::set_1_basics::c4_detect_single_char_xor::detect_message_bytes;
+use cryptopals_crypto_challenges::set_1_basics::c6_break_repeating_key_xor::break_repeating_key_xor_bytes;
+use cryptopals_crypto_challenges::set_1_basics::c8_detect_aes_in_ecb_mode::detect_aes_ecb_bytes;
+use cryptopals_crypto_challenges::set_2_block_crypto::c15_pkcs7_padding_validation::unpad_pkcs7_bytes;
+use cryptopals_crypto_challenges::set_2_block_crypto::c9_implement_pkcs_padding::pad_pkcs7_bytes;
+use cryptopals_crypto_challenges::set_5_diffie_hellman_and_friends::c33_implement_diffie_hellman::{
+    G, P,
+};
+use cryptopals_crypto_challenges::utils::aes;
+use cryptopals_crypto_challenges::utils::diffie_hellman::DH;
+use num_bigint::{BigUint, RandBigInt};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process;
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+const USAGE: &str = "Usage: cryptopals <command> [options] [FILE]
+
+Commands:
+    xor-break                     Recover the repeating xor key (c6)
+    single-xor [--lines]          Break single byte xor, or find the xored line (c3/c4)
+    detect-ecb                    Find the ECB encrypted line (c8)
+    aes ecb|cbc encrypt|decrypt   AES-128 with --key HEX [--iv HEX]
+    pad | unpad                   PKCS#7 with [--block N]
+    dh keygen                     Diffie-Hellman key pair, [--p HEX] [--g HEX]
+
+Options:
+    --in hex|base64|raw           Input encoding, raw by default
+    --out hex|base64|raw          Output encoding, raw by default (hex for dh)
+
+Input is read from FILE, or stdin if no FILE is given.";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Encoding {
+    Hex,
+    Base64,
+    Raw,
+}
+
+impl Encoding {
+    fn parse(s: &str) -> CliResult<Self> {
+        match s {
+            "hex" => Ok(Encoding::Hex),
+            "base64" => Ok(Encoding::Base64),
+            "raw" => Ok(Encoding::Raw),
+            _ => Err(format!("unknown encoding: {}", s).into()),
+        }
+    }
+
+    fn decode(self, data: &[u8]) -> CliResult<Vec<u8>> {
+        // Line breaks & padding whitespace aren't part of hex or base64 data
+        let text = || -> String {
+            String::from_utf8_lossy(data)
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect()
+        };
+        Ok(match self {
+            Encoding::Hex => hex::decode(text())?,
+            Encoding::Base64 => base64::decode(text())?,
+            Encoding::Raw => data.to_vec(),
+        })
+    }
+
+    fn encode(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Encoding::Hex => format!("{}\n", hex::encode(data)).into_bytes(),
+            Encoding::Base64 => format!("{}\n", base64::encode(data)).into_bytes(),
+            Encoding::Raw => data.to_vec(),
+        }
+    }
+}
+
+struct Args {
+    positional: Vec<String>,
+    input_enc: Encoding,
+    output_enc: Option<Encoding>,
+    key: Option<Vec<u8>>,
+    iv: Option<Vec<u8>>,
+    block: usize,
+    lines: bool,
+    p: Option<Vec<u8>>,
+    g: Option<Vec<u8>>,
+}
+
+impl Args {
+    fn parse(raw: &[String]) -> CliResult<Self> {
+        let mut args = Args {
+            positional: Vec::new(),
+            input_enc: Encoding::Raw,
+            output_enc: None,
+            key: None,
+            iv: None,
+            block: 16,
+            lines: false,
+            p: None,
+            g: None,
+        };
+
+        let mut iter = raw.iter();
+        while let Some(arg) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .ok_or_else(|| format!("missing value for {}", arg))
+            };
+            match arg.as_str() {
+                "--in" => args.input_enc = Encoding::parse(value()?)?,
+                "--out" => args.output_enc = Some(Encoding::parse(value()?)?),
+                "--key" => args.key = Some(hex::decode(value()?)?),
+                "--iv" => args.iv = Some(hex::decode(value()?)?),
+                "--block" => {
+                    args.block = value()?.parse()?;
+                    // PKCS#7 puts the padding size in each padding byte
+                    if !(1..=255).contains(&args.block) {
+                        return Err("--block must be between 1 and 255".into());
+                    }
+                }
+                "--p" => args.p = Some(hex::decode(value()?)?),
+                "--g" => args.g = Some(hex::decode(value()?)?),
+                "--lines" => args.lines = true,
+                _ if arg.starts_with("--") => return Err(format!("unknown option: {}", arg).into()),
+                _ => args.positional.push(arg.clone()),
+            }
+        }
+
+        Ok(args)
+    }
+
+    // Positional argument at idx, naming what was expected if missing
+    fn word(&self, idx: usize, expected: &str) -> CliResult<&str> {
+        self.positional
+            .get(idx)
+            .map(|s| s.as_str())
+            .ok_or_else(|| format!("expected {}", expected).into())
+    }
+
+    // Optional input file following the n subcommand words
+    fn read_raw(&self, n_words: usize) -> CliResult<Vec<u8>> {
+        match self.positional.get(n_words) {
+            Some(path) => Ok(fs::read(path)?),
+            None => {
+                let mut buf = Vec::new();
+                io::stdin().read_to_end(&mut buf)?;
+                Ok(buf)
+            }
+        }
+    }
+
+    fn read_input(&self, n_words: usize) -> CliResult<Vec<u8>> {
+        self.input_enc.decode(&self.read_raw(n_words)?)
+    }
+
+    // Input split into lines, each decoded on its own
+    fn read_input_lines(&self, n_words: usize) -> CliResult<Vec<Vec<u8>>> {
+        self.read_raw(n_words)?
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| self.input_enc.decode(line))
+            .collect()
+    }
+
+    fn key(&self) -> CliResult<&[u8]> {
+        self.key.as_deref().ok_or_else(|| "missing --key".into())
+    }
+}
+
+fn run(raw: &[String]) -> CliResult<Vec<u8>> {
+    let args = Args::parse(raw)?;
+    let out = args.output_enc.unwrap_or(Encoding::Raw);
+
+    let output = match args.word(0, "a command")? {
+        "xor-break" => out.encode(&break_repeating_key_xor_bytes(&args.read_input(1)?)),
+        "single-xor" => {
+            let message = if args.lines {
+                detect_message_bytes(&args.read_input_lines(1)?)
+            } else {
+                decipher_bytes(&args.read_input(1)?).0
+            };
+            out.encode(&message)
+        }
+        "detect-ecb" => {
+            let (line, n_blocks) = detect_aes_ecb_bytes(&args.read_input_lines(1)?);
+            format!("line {} ({} repeated blocks)\n", line, n_blocks).into_bytes()
+        }
+        "aes" => {
+            let mode = args.word(1, "ecb or cbc")?;
+            let direction = args.word(2, "encrypt or decrypt")?;
+            if !["ecb", "cbc"].contains(&mode) || !["encrypt", "decrypt"].contains(&direction) {
+                return Err(format!("unknown aes mode: {} {}", mode, direction).into());
+            }
+            let input = args.read_input(3)?;
+            let key = args.key()?;
+            let iv = args.iv.clone().unwrap_or_else(|| vec![0; 16]);
+            let result = match (mode, direction) {
+                ("ecb", "encrypt") => aes::aes128_ecb_encrypt(&input, key)?,
+                ("ecb", "decrypt") => aes::aes128_ecb_decrypt(&input, key)?,
+                ("cbc", "encrypt") => aes::aes128_cbc_encrypt(&input, key, &iv)?,
+                _ => aes::aes128_cbc_decrypt(&input, key, &iv)?,
+            };
+            out.encode(&result)
+        }
+        "pad" => out.encode(&pad_pkcs7_bytes(&args.read_input(1)?, args.block)?),
+        "unpad" => out.encode(&unpad_pkcs7_bytes(&args.read_input(1)?, args.block)?),
+        "dh" => {
+            let command = args.word(1, "keygen")?;
+            if command != "keygen" {
+                return Err(format!("unknown dh command: {}", command).into());
+            }
+            let out = args.output_enc.unwrap_or(Encoding::Hex);
+            if out == Encoding::Raw {
+                return Err("dh keygen needs hex or base64 output".into());
+            }
+            let p = args.p.clone().unwrap_or_else(|| hex::decode(P).unwrap());
+            let g = args
+                .g
+                .clone()
+                .unwrap_or_else(|| BigUint::from(G).to_bytes_be());
+            let modulus = BigUint::from_bytes_be(&p);
+            if modulus <= BigUint::from(2u8) {
+                return Err("dh modulus must be greater than 2".into());
+            }
+            let dh = DH::new(&p, &g);
+
+            let private_key = rand::thread_rng()
+                .gen_biguint_range(&BigUint::from(1u8), &modulus)
+                .to_bytes_be();
+            let public_key = dh.gen_pub_key(&private_key);
+
+            let mut keys = out.encode(&private_key);
+            keys.extend(out.encode(&public_key));
+            keys
+        }
+        cmd => return Err(format!("unknown command: {}", cmd).into()),
+    };
+
+    Ok(output)
+}
+
+fn main() {
+    let raw: Vec<String> = std::env::args().skip(1).collect();
+    if raw.is_empty() || raw[0] == "--help" || raw[0] == "-h" {
+        println!("{}", USAGE);
+        return;
+    }
+
+    match run(&raw) {
+        Ok(output) => {
+            io::stdout()
+                .write_all(&output)
+                .expect("Error writing output!");
+        }
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use cryptopals_crypto_challenges::registry::{self, Challenge, Outcome};
+use std::panic;
+use std::process;
+
+const USAGE: &str = "Usage: runner [--list] [--set N]... [N]...
+
+Runs the given challenges & whole sets (all of them by default) and prints
+a report. Exits with an error if any challenge isn't solved.
+
+Attacks are slow without optimizations, run with `cargo run --release`.";
+
+const TITLE_WIDTH: usize = 44;
+const SECRET_WIDTH: usize = 40;
+
+// Escapes control characters & cuts s to width chars
+fn fit(s: &str, width: usize) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        if c.is_control() {
+            escaped.extend(c.escape_debug());
+        } else {
+            escaped.push(c);
+        }
+    }
+    if escaped.chars().count() <= width {
+        escaped
+    } else {
+        let cut: String = escaped.chars().take(width - 3).collect();
+        format!("{}...", cut)
+    }
+}
+
+// Runs c, turning a panic into an error so the rest still get reported
+fn run_isolated(c: &Challenge) -> Result<Outcome, String> {
+    match panic::catch_unwind(|| c.run()) {
+        Ok(result) => result.map_err(|err| err.to_string()),
+        Err(payload) => {
+            let msg = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown".to_owned());
+            Err(format!("panicked: {}", msg))
+        }
+    }
+}
+
+fn select(args: &[String]) -> Result<Vec<&'static Challenge>, String> {
+    let mut selected: Vec<&'static Challenge> = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let found = if arg == "--set" {
+            let set = iter.next().ok_or("missing value for --set")?;
+            let set: u32 = set.parse().map_err(|_| format!("bad set: {}", set))?;
+            let challenges = registry::in_set(set);
+            if challenges.is_empty() {
+                return Err(format!("no challenges in set {}", set));
+            }
+            challenges
+        } else {
+            let number: u32 = arg.parse().map_err(|_| format!("bad challenge: {}", arg))?;
+            vec![registry::find(number).ok_or(format!("no challenge {}", number))?]
+        };
+        for challenge in found {
+            if !selected.iter().any(|c| c.number == challenge.number) {
+                selected.push(challenge);
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        selected = registry::challenges().collect();
+    }
+    selected.sort_by_key(|c| c.number);
+    Ok(selected)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+    let list_only = args.iter().any(|a| a == "--list");
+    let args: Vec<String> = args.into_iter().filter(|a| a != "--list").collect();
+
+    let selected = match select(&args) {
+        Ok(selected) => selected,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if list_only {
+        for c in selected {
+            println!("{:>3} {:>4}  {}", c.number, c.set, c.title);
+        }
+        return;
+    }
+
+    println!(
+        "{:>3} {:>4}  {:<tw$}  {:<6} {:>9} {:>9}  Secret",
+        "#",
+        "Set",
+        "Title",
+        "Result",
+        "Queries",
+        "Time",
+        tw = TITLE_WIDTH
+    );
+
+    // Panics are reported as errors in the table instead
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut n_failed = 0;
+    for c in &selected {
+        let (result, queries, time, secret) = match run_isolated(c) {
+            Ok(outcome) => {
+                if !outcome.solved {
+                    n_failed += 1;
+                }
+                (
+                    if outcome.solved { "ok" } else { "FAIL" },
+                    outcome.queries.map_or("-".to_owned(), |q| q.to_string()),
+                    format!("{:.2}s", outcome.elapsed.as_secs_f64()),
+                    outcome.secret,
+                )
+            }
+            Err(err) => {
+                n_failed += 1;
+                ("ERROR", "-".to_owned(), "-".to_owned(), err)
+            }
+        };
+        println!(
+            "{:>3} {:>4}  {:<tw$}  {:<6} {:>9} {:>9}  {}",
+            c.number,
+            c.set,
+            fit(c.title, TITLE_WIDTH),
+            result,
+            queries,
+            time,
+            fit(&secret, SECRET_WIDTH),
+            tw = TITLE_WIDTH
+        );
+    }
+
+    println!(
+        "\n{} of {} challenges solved",
+        selected.len() - n_failed,
+        selected.len()
+    );
+    if n_failed > 0 {
+        process::exit(1);
+    }
+}
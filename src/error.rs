@@ -0,0 +1,98 @@
+use block_modes::BlockModeError;
+use std::fmt;
+
+// Errors of the crypto primitives & the oracles built on them, so callers
+// get to decide what to do with bad input rather than panicking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CryptoError {
+    // Key of the given length where the cipher wants another one
+    InvalidKeyLength(usize),
+    // Block or IV of the given length where a full block is expected
+    InvalidBlockLength(usize),
+    // Hash state of the given length the compression function can't take
+    InvalidStateLength(usize),
+    // Message of the given length too long for the block it is padded into
+    MessageTooLong(usize),
+    BadPadding,
+    // Malformed hex, base64, UTF-8 or key=value input
+    InvalidEncoding(String),
+    // Authentication tag doesn't match
+    InvalidTag,
+    // Step taken out of order in a protocol
+    ProtocolState(String),
+    // Input an oracle won't process, e.g. with metacharacters
+    OracleRefused(String),
+    // Attack ran out of candidates, e.g. no guess matched the oracle
+    AttackFailed(String),
+    // Input file couldn't be opened or read
+    Io(String),
+}
+
+pub type Result<T> = std::result::Result<T, CryptoError>;
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CryptoError::InvalidKeyLength(len) => write!(f, "invalid key length: {}", len),
+            CryptoError::InvalidBlockLength(len) => write!(f, "invalid block length: {}", len),
+            CryptoError::InvalidStateLength(len) => write!(f, "invalid state length: {}", len),
+            CryptoError::MessageTooLong(len) => write!(f, "message too long: {}", len),
+            CryptoError::BadPadding => write!(f, "bad padding"),
+            CryptoError::InvalidEncoding(msg) => write!(f, "invalid encoding: {}", msg),
+            CryptoError::InvalidTag => write!(f, "authentication tag mismatch"),
+            CryptoError::ProtocolState(msg) => write!(f, "protocol state error: {}", msg),
+            CryptoError::OracleRefused(msg) => write!(f, "oracle refused input: {}", msg),
+            CryptoError::AttackFailed(msg) => write!(f, "attack failed: {}", msg),
+            CryptoError::Io(msg) => write!(f, "I/O error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+impl From<BlockModeError> for CryptoError {
+    fn from(_: BlockModeError) -> Self {
+        CryptoError::BadPadding
+    }
+}
+
+impl From<hex::FromHexError> for CryptoError {
+    fn from(err: hex::FromHexError) -> Self {
+        CryptoError::InvalidEncoding(err.to_string())
+    }
+}
+
+impl From<base64::DecodeError> for CryptoError {
+    fn from(err: base64::DecodeError) -> Self {
+        CryptoError::InvalidEncoding(err.to_string())
+    }
+}
+
+impl From<std::io::Error> for CryptoError {
+    fn from(err: std::io::Error) -> Self {
+        CryptoError::Io(err.to_string())
+    }
+}
+
+impl From<std::string::FromUtf8Error> for CryptoError {
+    fn from(err: std::string::FromUtf8Error) -> Self {
+        CryptoError::InvalidEncoding(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_crypto_error() {
+        let err: CryptoError = hex::decode("zz").unwrap_err().into();
+        assert!(matches!(err, CryptoError::InvalidEncoding(_)));
+        let err: CryptoError = base64::decode("!").unwrap_err().into();
+        assert!(matches!(err, CryptoError::InvalidEncoding(_)));
+        assert_eq!(
+            CryptoError::InvalidKeyLength(5).to_string(),
+            "invalid key length: 5"
+        );
+    }
+}
@@ -1,6 +1,11 @@
+pub mod error;
+pub mod registry;
 pub mod set_1_basics;
 pub mod set_2_block_crypto;
 pub mod set_5_diffie_hellman_and_friends;
+pub mod set_6_rsa_and_dsa;
+pub mod set_7_hashes;
+pub mod set_8_abstract_algebra;
 pub mod utils;
 
 #[cfg(test)]
@@ -0,0 +1,101 @@
+use crate::error::Result;
+use std::time::{Duration, Instant};
+
+mod set_1;
+mod set_2;
+mod set_5;
+mod set_6;
+mod set_7;
+mod set_8;
+
+// Result of running a challenge end to end against its known answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub solved: bool,
+    // Recovered secret (or forged artifact) in printable form
+    pub secret: String,
+    // Queries made to the oracle, for attacks whose oracle counts them
+    pub queries: Option<usize>,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    fn new(solved: bool, secret: impl Into<String>) -> Self {
+        Self {
+            solved,
+            secret: secret.into(),
+            queries: None,
+            elapsed: Duration::default(),
+        }
+    }
+
+    fn with_queries(mut self, queries: usize) -> Self {
+        self.queries = Some(queries);
+        self
+    }
+}
+
+pub struct Challenge {
+    pub number: u32,
+    pub set: u32,
+    pub title: &'static str,
+    solve: fn() -> Result<Outcome>,
+}
+
+impl Challenge {
+    // Runs the attack, timing it
+    pub fn run(&self) -> Result<Outcome> {
+        let start = Instant::now();
+        let mut outcome = (self.solve)()?;
+        outcome.elapsed = start.elapsed();
+        Ok(outcome)
+    }
+}
+
+// All registered challenges, ordered by number
+pub fn challenges() -> impl Iterator<Item = &'static Challenge> {
+    set_1::CHALLENGES
+        .iter()
+        .chain(set_2::CHALLENGES)
+        .chain(set_5::CHALLENGES)
+        .chain(set_6::CHALLENGES)
+        .chain(set_7::CHALLENGES)
+        .chain(set_8::CHALLENGES)
+}
+
+pub fn find(number: u32) -> Option<&'static Challenge> {
+    challenges().find(|c| c.number == number)
+}
+
+pub fn in_set(set: u32) -> Vec<&'static Challenge> {
+    challenges().filter(|c| c.set == set).collect()
+}
+
+// Relative paths would depend on the directory the runner is started from
+fn data_file(name: &str) -> String {
+    format!("{}/files/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let numbers: Vec<u32> = challenges().map(|c| c.number).collect();
+        assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+        assert!(challenges().all(|c| c.set == (c.number - 1) / 8 + 1));
+        assert_eq!(in_set(1).len(), 8);
+        assert!(in_set(3).is_empty());
+        assert_eq!(find(47).unwrap().set, 6);
+        assert!(find(17).is_none());
+
+        for number in [1, 3, 9, 13, 33, 45] {
+            let outcome = find(number).unwrap().run().unwrap();
+            assert!(outcome.solved, "challenge {} not solved", number);
+        }
+        let outcome = find(46).unwrap().run().unwrap();
+        assert!(outcome.solved);
+        assert_eq!(outcome.queries, Some(1024));
+    }
+}
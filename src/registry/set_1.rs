@@ -0,0 +1,119 @@
+use super::{data_file, Challenge, Outcome};
+use crate::error::Result;
+use crate::set_1_basics::c1_convert_hex_to_base64::convert_hex_to_base64;
+use crate::set_1_basics::c2_fixed_xor::fixed_xor;
+use crate::set_1_basics::c3_single_byte_xor_cypher::decipher_message;
+use crate::set_1_basics::c4_detect_single_char_xor::detect_message;
+use crate::set_1_basics::c5_repeating_key_xor::repeated_key_xor;
+use crate::set_1_basics::c6_break_repeating_key_xor::break_repeating_key_xor;
+use crate::set_1_basics::c7_aes_in_ecb_mode::decrypt_message;
+use crate::set_1_basics::c8_detect_aes_in_ecb_mode::detect_aes_ecb_encryption;
+
+pub(super) const CHALLENGES: &[Challenge] = &[
+    Challenge {
+        number: 1,
+        set: 1,
+        title: "Convert hex to base64",
+        solve: c1,
+    },
+    Challenge {
+        number: 2,
+        set: 1,
+        title: "Fixed XOR",
+        solve: c2,
+    },
+    Challenge {
+        number: 3,
+        set: 1,
+        title: "Single-byte XOR cipher",
+        solve: c3,
+    },
+    Challenge {
+        number: 4,
+        set: 1,
+        title: "Detect single-character XOR",
+        solve: c4,
+    },
+    Challenge {
+        number: 5,
+        set: 1,
+        title: "Implement repeating-key XOR",
+        solve: c5,
+    },
+    Challenge {
+        number: 6,
+        set: 1,
+        title: "Break repeating-key XOR",
+        solve: c6,
+    },
+    Challenge {
+        number: 7,
+        set: 1,
+        title: "AES in ECB mode",
+        solve: c7,
+    },
+    Challenge {
+        number: 8,
+        set: 1,
+        title: "Detect AES in ECB mode",
+        solve: c8,
+    },
+];
+
+fn c1() -> Result<Outcome> {
+    let base64 = convert_hex_to_base64("49276d206b696c6c696e6720796f757220627261696e206c696b65206120706f69736f6e6f7573206d757368726f6f6d");
+    let solved = base64 == "SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t";
+    Ok(Outcome::new(solved, base64))
+}
+
+fn c2() -> Result<Outcome> {
+    let xor = fixed_xor(
+        "1c0111001f010100061a024b53535009181c",
+        "686974207468652062756c6c277320657965",
+    )?;
+    let solved = xor == "746865206b696420646f6e277420706c6179";
+    Ok(Outcome::new(solved, xor))
+}
+
+fn c3() -> Result<Outcome> {
+    let (message, _) =
+        decipher_message("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736")?;
+    Ok(Outcome::new(
+        message == "Cooking MC's like a pound of bacon",
+        message,
+    ))
+}
+
+fn c4() -> Result<Outcome> {
+    let message = detect_message(&data_file("set_1/4_strings.txt"))?;
+    Ok(Outcome::new(
+        message == "Now that the party is jumping\n",
+        message,
+    ))
+}
+
+fn c5() -> Result<Outcome> {
+    let xor = repeated_key_xor(
+        "Burning 'em, if you ain't quick and nimble\nI go crazy when I hear a cymbal",
+        "ICE",
+    );
+    let solved = xor == "0b3637272a2b2e63622c2e69692a23693a2a3c6324202d623d63343c2a26226324272765272a282b2f20430a652e2c652a3124333a653e2b2027630c692b20283165286326302e27282f";
+    Ok(Outcome::new(solved, xor))
+}
+
+fn c6() -> Result<Outcome> {
+    let key = break_repeating_key_xor(&data_file("set_1/6_base64.txt"))?;
+    Ok(Outcome::new(key == "Terminator X: Bring the noise", key))
+}
+
+fn c7() -> Result<Outcome> {
+    let message = decrypt_message(&data_file("set_1/7_base64.txt"), "YELLOW SUBMARINE")?;
+    let solved = message.starts_with("I'm back and I'm ringin' the bell \n");
+    Ok(Outcome::new(solved, message))
+}
+
+fn c8() -> Result<Outcome> {
+    let (line, n_blocks) = detect_aes_ecb_encryption(&data_file("set_1/8_hex.txt"))?;
+    let secret = format!("line {} ({} repeated blocks)", line, n_blocks);
+    Ok(Outcome::new(line == 132, secret))
+}
@@ -0,0 +1,142 @@
+use super::{Challenge, Outcome};
+use crate::error::Result;
+use crate::set_2_block_crypto::c10_implement_cbc_mode::{aes_128_cbc_decrypt, aes_128_cbc_encrypt};
+use crate::set_2_block_crypto::c11_ecb_cbc_detection_oracle::{detect_mode_with, ModeOracle};
+use crate::set_2_block_crypto::c12_byte_at_a_time_ecb_decryption as c12;
+use crate::set_2_block_crypto::c13_ecb_cut_and_paste::{
+    create_admin_profile_with, decode_user_profile, ProfileOracle,
+};
+use crate::set_2_block_crypto::c14_byte_at_a_time_ecb_decryption as c14;
+use crate::set_2_block_crypto::c15_pkcs7_padding_validation::validate_pkcs7_padding;
+use crate::set_2_block_crypto::c16_cbc_bitflipping_attacks::{
+    bit_flip_attack_with, decrypt_and_find_admin, UserdataOracle,
+};
+use crate::set_2_block_crypto::c9_implement_pkcs_padding::pad_pkcs7;
+
+// Secret appended by the c12 & c14 oracles, with the last byte of padding
+// picked up by the attack
+const ROLLIN: &str = "Rollin' in my 5.0\nWith my rag-top down so my hair can blow\nThe girlies on standby waving just to say hi\nDid you stop? No, I just drove by\n\u{1}";
+
+pub(super) const CHALLENGES: &[Challenge] = &[
+    Challenge {
+        number: 9,
+        set: 2,
+        title: "Implement PKCS#7 padding",
+        solve: c9,
+    },
+    Challenge {
+        number: 10,
+        set: 2,
+        title: "Implement CBC mode",
+        solve: c10,
+    },
+    Challenge {
+        number: 11,
+        set: 2,
+        title: "An ECB/CBC detection oracle",
+        solve: c11,
+    },
+    Challenge {
+        number: 12,
+        set: 2,
+        title: "Byte-at-a-time ECB decryption (Simple)",
+        solve: c12,
+    },
+    Challenge {
+        number: 13,
+        set: 2,
+        title: "ECB cut-and-paste",
+        solve: c13,
+    },
+    Challenge {
+        number: 14,
+        set: 2,
+        title: "Byte-at-a-time ECB decryption (Harder)",
+        solve: c14,
+    },
+    Challenge {
+        number: 15,
+        set: 2,
+        title: "PKCS#7 padding validation",
+        solve: c15,
+    },
+    Challenge {
+        number: 16,
+        set: 2,
+        title: "CBC bitflipping attacks",
+        solve: c16,
+    },
+];
+
+fn c9() -> Result<Outcome> {
+    let padded = pad_pkcs7("YELLOW SUBMARINE", 20)?;
+    Ok(Outcome::new(
+        padded == "YELLOW SUBMARINE\x04\x04\x04\x04",
+        padded,
+    ))
+}
+
+fn c10() -> Result<Outcome> {
+    let msg = "This is some secret message. Do not reveal.";
+    let iv = "\x00".repeat(16);
+    let encrypted = aes_128_cbc_encrypt(msg, "YELLOW SUBMARINE", &iv)?;
+    let decrypted = aes_128_cbc_decrypt(&encrypted, "YELLOW SUBMARINE", &iv)?;
+    Ok(Outcome::new(decrypted == msg, encrypted))
+}
+
+fn c11() -> Result<Outcome> {
+    let oracle = ModeOracle::new();
+    let n_trials = 100;
+    let mut n_correct = 0;
+    for _ in 0..n_trials {
+        let (predicted_mode, actual_mode) = detect_mode_with(&oracle)?;
+        if predicted_mode == actual_mode {
+            n_correct += 1;
+        }
+    }
+    let secret = format!("{}/{} modes detected", n_correct, n_trials);
+    Ok(Outcome::new(n_correct == n_trials, secret).with_queries(oracle.queries()))
+}
+
+fn c12() -> Result<Outcome> {
+    let oracle = c12::EcbOracle::new();
+    let block_size = c12::detect_block_size_with(&oracle)?;
+    let secret = c12::decrypt_unknown_with(&oracle, block_size)?;
+    Ok(Outcome::new(secret == ROLLIN, secret).with_queries(oracle.queries()))
+}
+
+fn c13() -> Result<Outcome> {
+    let oracle = ProfileOracle::new();
+    let (cipherbytes, email) = create_admin_profile_with(&oracle)?;
+    let profile = decode_user_profile(&cipherbytes)?;
+    let solved = profile.get("role").map(|r| r.as_str()) == Some("admin")
+        && profile.get("email") == Some(&email);
+    let secret = format!("email={}&role=admin", email);
+    Ok(Outcome::new(solved, secret).with_queries(oracle.queries()))
+}
+
+fn c14() -> Result<Outcome> {
+    let oracle = c14::EcbOracle::new();
+    let block_size = c14::detect_block_size_with(&oracle)?;
+    let prefix_len = c14::detect_prefix_len_with(&oracle, block_size)?;
+    let secret = c14::decrypt_unknown_with(&oracle, block_size, prefix_len)?;
+    Ok(Outcome::new(secret == ROLLIN, secret).with_queries(oracle.queries()))
+}
+
+fn c15() -> Result<Outcome> {
+    let solved = validate_pkcs7_padding("ICE ICE BABY\x04\x04\x04\x04")
+        && !validate_pkcs7_padding("ICE ICE BABY\x05\x05\x05\x05")
+        && !validate_pkcs7_padding("ICE ICE BABY\x01\x02\x03\x04");
+    Ok(Outcome::new(solved, "ICE ICE BABY"))
+}
+
+fn c16() -> Result<Outcome> {
+    let oracle = UserdataOracle::new();
+    let cipherbytes = bit_flip_attack_with(&oracle, 16)?;
+    let idx = decrypt_and_find_admin(&cipherbytes)?;
+    let secret = match idx {
+        Some(i) => format!(";admin=true; at byte {}", i),
+        None => "no admin".to_owned(),
+    };
+    Ok(Outcome::new(idx.is_some(), secret).with_queries(oracle.queries()))
+}
@@ -0,0 +1,63 @@
+use super::{Challenge, Outcome};
+use crate::error::Result;
+use crate::set_5_diffie_hellman_and_friends::c33_implement_diffie_hellman::{G, P};
+use crate::set_5_diffie_hellman_and_friends::{c34_implement_mitm_key_fixing, c35_dh_malicious_g};
+use crate::utils::diffie_hellman::DH;
+use num_bigint::{BigUint, RandBigInt};
+
+// Toy group & keys of the c34 & c35 tests
+const P_SMALL: &[u8] = &[36, 32];
+const G_SMALL: &[u8] = &[5, 6];
+const PK_A: &[u8] = &[36];
+const PK_B: &[u8] = &[12];
+const IV_A: &[u8] = b"YELLOW SUBMARINE";
+const IV_B: &[u8] = b"PURPLE SUBMARINE";
+const MSG: &[u8] = b"hello B!";
+
+pub(super) const CHALLENGES: &[Challenge] = &[
+    Challenge {
+        number: 33,
+        set: 5,
+        title: "Implement Diffie-Hellman",
+        solve: c33,
+    },
+    Challenge {
+        number: 34,
+        set: 5,
+        title: "Implement a MITM key-fixing attack on Diffie-Hellman with parameter injection",
+        solve: c34,
+    },
+    Challenge {
+        number: 35,
+        set: 5,
+        title: "Implement DH with negotiated groups, and break with malicious \"g\" parameters",
+        solve: c35,
+    },
+];
+
+fn c33() -> Result<Outcome> {
+    let p = hex::decode(P)?;
+    let dh = DH::new(&p, &BigUint::from(G).to_bytes_be());
+    let p = BigUint::from_bytes_be(&p);
+
+    let a = rand::thread_rng().gen_biguint_below(&p).to_bytes_be();
+    let b = rand::thread_rng().gen_biguint_below(&p).to_bytes_be();
+    let s_a = dh.gen_session_key(&dh.gen_pub_key(&b), &a);
+    let s_b = dh.gen_session_key(&dh.gen_pub_key(&a), &b);
+    Ok(Outcome::new(s_a == s_b, hex::encode(s_a)))
+}
+
+fn c34() -> Result<Outcome> {
+    let (msg_a, msg_b) = c34_implement_mitm_key_fixing::simulate_crack(
+        P_SMALL, G_SMALL, PK_A, PK_B, IV_A, IV_B, MSG,
+    )?;
+    let solved = msg_a == MSG && msg_b == MSG;
+    Ok(Outcome::new(solved, String::from_utf8_lossy(&msg_a)))
+}
+
+fn c35() -> Result<Outcome> {
+    let (msg_a, msg_b) =
+        c35_dh_malicious_g::simulate_crack(P_SMALL, G_SMALL, PK_A, PK_B, IV_A, IV_B, MSG)?;
+    let solved = msg_a == MSG && msg_b == MSG;
+    Ok(Outcome::new(solved, String::from_utf8_lossy(&msg_a)))
+}
@@ -0,0 +1,83 @@
+use super::{Challenge, Outcome};
+use crate::error::Result;
+use crate::set_6_rsa_and_dsa::c45_dsa_parameter_tampering::{
+    challenge_dsa, forge_zero_g_signature, magic_signature, Verifier,
+};
+use crate::set_6_rsa_and_dsa::c46_rsa_parity_oracle::{recover_plaintext, ParityOracle};
+use crate::set_6_rsa_and_dsa::c47_bleichenbacher_pkcs_padding_oracle::{
+    bleichenbacher_attack, PaddingOracle,
+};
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+pub(super) const CHALLENGES: &[Challenge] = &[
+    Challenge {
+        number: 45,
+        set: 6,
+        title: "DSA parameter tampering",
+        solve: c45,
+    },
+    Challenge {
+        number: 46,
+        set: 6,
+        title: "RSA parity oracle",
+        solve: c46,
+    },
+    Challenge {
+        number: 47,
+        set: 6,
+        title: "Bleichenbacher's PKCS 1.5 Padding Oracle (Simple Case)",
+        solve: c47,
+    },
+    Challenge {
+        number: 48,
+        set: 6,
+        title: "Bleichenbacher's PKCS 1.5 Padding Oracle (Complete Case)",
+        solve: c48,
+    },
+];
+
+fn c45() -> Result<Outcome> {
+    let dsa = challenge_dsa();
+    let (_, y) = dsa.gen_key_pair();
+    let msgs = [&b"Hello, world"[..], b"Goodbye, world"];
+
+    let zero = BigUint::zero();
+    let lax = Verifier::new(&zero, false);
+    let forged = forge_zero_g_signature(&lax.dsa.q);
+    let zero_g = msgs.iter().all(|msg| lax.verify(msg, &forged, &zero));
+
+    let strict = Verifier::new(&(&dsa.p + 1_u8), true);
+    let magic = magic_signature(&y, &dsa.p, &dsa.q);
+    let p_plus_one_g = msgs.iter().all(|msg| strict.verify(msg, &magic, &y));
+
+    let secret = format!("r = {:x}, s = {:x}", magic.r, magic.s);
+    Ok(Outcome::new(zero_g && p_plus_one_g, secret))
+}
+
+fn c46() -> Result<Outcome> {
+    let oracle = ParityOracle::new(1024);
+    let c = oracle.encrypt_secret();
+    let plaintext = recover_plaintext(&oracle, &c, None);
+    let solved =
+        plaintext == b"That's why I found you don't play around with the Funky Cold Medina";
+    Ok(Outcome::new(solved, String::from_utf8_lossy(&plaintext)).with_queries(oracle.queries()))
+}
+
+fn bleichenbacher(bits: u64) -> Result<Outcome> {
+    let oracle = PaddingOracle::new(bits);
+    let msg = b"kick it, CC";
+    let recovered = bleichenbacher_attack(&oracle, &oracle.encrypt(msg)?);
+    Ok(
+        Outcome::new(recovered == msg, String::from_utf8_lossy(&recovered))
+            .with_queries(oracle.queries()),
+    )
+}
+
+fn c47() -> Result<Outcome> {
+    bleichenbacher(256)
+}
+
+fn c48() -> Result<Outcome> {
+    bleichenbacher(768)
+}
@@ -0,0 +1,154 @@
+use super::{Challenge, Outcome};
+use crate::error::Result;
+use crate::set_7_hashes::c49_cbc_mac_message_forgery::{
+    forge_transfer, forge_tx_list, process_transfer, process_tx_list, sign_tx_list,
+};
+use crate::set_7_hashes::c50_hashing_with_cbc_mac::{cbc_mac_hash, forge_snippet};
+use crate::set_7_hashes::c51_compression_ratio_side_channel::{recover_session_id, EncryptionMode};
+use crate::set_7_hashes::c52_iterated_hash_multicollisions::cascade_collision;
+use crate::set_7_hashes::c53_kelsey_schneier_expandable_messages::second_preimage;
+use crate::set_7_hashes::c54_kelsey_kohno_nostradamus_attack::Prediction;
+use crate::set_7_hashes::c55_md4_collisions::find_collision;
+use crate::set_7_hashes::c56_rc4_single_byte_biases::{recover_cookie, CookieOracle};
+use crate::utils::md4::md4;
+use crate::utils::merkle_damgard::{AesMD, MerkleDamgard, BLOCK_SIZE};
+
+pub(super) const CHALLENGES: &[Challenge] = &[
+    Challenge {
+        number: 49,
+        set: 7,
+        title: "CBC-MAC Message Forgery",
+        solve: c49,
+    },
+    Challenge {
+        number: 50,
+        set: 7,
+        title: "Hashing with CBC-MAC",
+        solve: c50,
+    },
+    Challenge {
+        number: 51,
+        set: 7,
+        title: "Compression Ratio Side-Channel Attacks",
+        solve: c51,
+    },
+    Challenge {
+        number: 52,
+        set: 7,
+        title: "Iterated Hash Function Multicollisions",
+        solve: c52,
+    },
+    Challenge {
+        number: 53,
+        set: 7,
+        title: "Kelsey and Schneier's Expandable Messages",
+        solve: c53,
+    },
+    Challenge {
+        number: 54,
+        set: 7,
+        title: "Kelsey and Kohno's Nostradamus Attack",
+        solve: c54,
+    },
+    Challenge {
+        number: 55,
+        set: 7,
+        title: "MD4 Collisions",
+        solve: c55,
+    },
+    Challenge {
+        number: 56,
+        set: 7,
+        title: "RC4 Single-Byte Biases",
+        solve: c56,
+    },
+];
+
+fn c49() -> Result<Outcome> {
+    let forged = forge_transfer("123", "666", 1_000_000)?;
+    let attacker_iv = process_transfer(&forged)
+        .is_some_and(|t| t.from == "123" && t.to == "666" && t.amount == 1_000_000);
+
+    let captured = sign_tx_list("123", &[("456", 10), ("789", 20)])?;
+    let forged = forge_tx_list(&captured, "666", 1_000_000)?;
+    let fixed_iv = process_tx_list(&forged).is_some_and(|transfers| {
+        transfers.iter().all(|t| t.from == "123")
+            && transfers
+                .iter()
+                .any(|t| t.to == "666" && t.amount == 1_000_000)
+    });
+
+    Ok(Outcome::new(attacker_iv && fixed_iv, "123 -> 666: 1000000"))
+}
+
+fn c50() -> Result<Outcome> {
+    let target_hash = cbc_mac_hash(b"alert('MZA who was that?');\n")?;
+    let prefix = "alert('Ayo, the Wu is back!');";
+    let forged = forge_snippet(&target_hash, prefix)?;
+    let solved = forged.starts_with(prefix.as_bytes()) && cbc_mac_hash(&forged)? == target_hash;
+    Ok(Outcome::new(solved, String::from_utf8_lossy(&forged)))
+}
+
+fn c51() -> Result<Outcome> {
+    let ctr = recover_session_id(&EncryptionMode::CTR)?;
+    let cbc = recover_session_id(&EncryptionMode::CBC)?;
+    let session_id = "TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=";
+    Ok(Outcome::new(ctr == session_id && cbc == session_id, ctr))
+}
+
+// Queries of the hash attacks are compression function calls
+fn c52() -> Result<Outcome> {
+    let f = AesMD::new(2)?;
+    let g = AesMD::new(4)?;
+    let (m1, m2) = cascade_collision(&f, &g)?;
+    let queries = f.calls() + g.calls();
+
+    let h = |m: &[u8]| -> Result<Vec<u8>> { Ok([f.hash(m)?, g.hash(m)?].concat()) };
+    let digest = h(&m1)?;
+    let solved = m1 != m2 && digest == h(&m2)?;
+    Ok(Outcome::new(solved, hex::encode(digest)).with_queries(queries))
+}
+
+fn c53() -> Result<Outcome> {
+    let h = AesMD::new(3)?;
+    let k = 10;
+    let msg: Vec<u8> = (0..BLOCK_SIZE << k).map(|_| rand::random::<u8>()).collect();
+    let forged = second_preimage(&h, &msg, k)?;
+    let queries = h.calls();
+
+    let digest = h.hash(&forged)?;
+    let solved = forged != msg && digest == h.hash(&msg)?;
+    Ok(Outcome::new(solved, hex::encode(digest)).with_queries(queries))
+}
+
+fn c54() -> Result<Outcome> {
+    let h = AesMD::new(3)?;
+    let prediction = Prediction::commit(&h, 8, 4)?;
+    let scores = b"Yankees 5 Red Sox 3; Cubs 2 Cardinals 7; Giants 4 Dodgers 4;";
+    let forged = prediction.forge(&h, scores)?;
+    let queries = h.calls();
+
+    let solved = forged.starts_with(scores) && h.hash(&forged)? == prediction.hash;
+    Ok(Outcome::new(solved, hex::encode(&prediction.hash)).with_queries(queries))
+}
+
+fn c55() -> Result<Outcome> {
+    Ok(match find_collision(1 << 22) {
+        Some((m1, m2, _)) => {
+            let digest = md4(&m1);
+            Outcome::new(m1 != m2 && digest == md4(&m2), hex::encode(digest))
+        }
+        None => Outcome::new(false, "no collision"),
+    })
+}
+
+fn c56() -> Result<Outcome> {
+    // Two byte cookie, as the full one needs about 2^29 encryptions
+    let cookie = b"BE";
+    let oracle = CookieOracle::with_cookie(cookie);
+    let recovered = recover_cookie(&oracle, 1 << 22)?;
+    Ok(
+        Outcome::new(recovered == cookie, String::from_utf8_lossy(&recovered))
+            .with_queries(oracle.queries()),
+    )
+}
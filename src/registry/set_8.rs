@@ -0,0 +1,194 @@
+use super::{Challenge, Outcome};
+use crate::error::{CryptoError, Result};
+use crate::set_8_abstract_algebra::c57_diffie_hellman_small_subgroup_confinement::{
+    self as c57, Bob,
+};
+use crate::set_8_abstract_algebra::c58_pollard_kangaroo as c58;
+use crate::set_8_abstract_algebra::c59_ecdh_invalid_curve_attack as c59;
+use crate::set_8_abstract_algebra::c60_single_coordinate_ladders_twist_attack as c60;
+use crate::set_8_abstract_algebra::c61_duplicate_signature_key_selection::{ecdsa_dsks, rsa_dsks};
+use crate::set_8_abstract_algebra::c62_biased_nonce_ecdsa_key_recovery::{recover_key, Signer};
+use crate::set_8_abstract_algebra::c63_gcm_forbidden_attack::{
+    self as c63, forge_tag, key_candidates, Authenticated,
+};
+use crate::set_8_abstract_algebra::c64_gcm_truncated_mac_attack as c64;
+use crate::utils::bitwise::xor_bytes;
+use crate::utils::ecdsa::ECDSA;
+use crate::utils::gf128::ghash;
+use crate::utils::rsa::RSA;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::Rng;
+
+pub(super) const CHALLENGES: &[Challenge] = &[
+    Challenge {
+        number: 57,
+        set: 8,
+        title: "Diffie-Hellman Revisited: Small Subgroup Confinement",
+        solve: c57,
+    },
+    Challenge {
+        number: 58,
+        set: 8,
+        title: "Pollard's Method for Catching Kangaroos",
+        solve: c58,
+    },
+    Challenge {
+        number: 59,
+        set: 8,
+        title: "Elliptic Curve Diffie-Hellman and Invalid-Curve Attacks",
+        solve: c59,
+    },
+    Challenge {
+        number: 60,
+        set: 8,
+        title: "Single-Coordinate Ladders and Insecure Twists",
+        solve: c60,
+    },
+    Challenge {
+        number: 61,
+        set: 8,
+        title: "Duplicate-Signature Key Selection in ECDSA (and RSA)",
+        solve: c61,
+    },
+    Challenge {
+        number: 62,
+        set: 8,
+        title: "Key-Recovery Attacks on ECDSA with Biased Nonces",
+        solve: c62,
+    },
+    Challenge {
+        number: 63,
+        set: 8,
+        title: "Key-Recovery Attacks on GCM with Repeated Nonces",
+        solve: c63,
+    },
+    Challenge {
+        number: 64,
+        set: 8,
+        title: "Key-Recovery Attacks on GCM with a Truncated MAC",
+        solve: c64,
+    },
+];
+
+fn c57() -> Result<Outcome> {
+    let (p, g, q) = c57::challenge_params();
+    let bob = Bob::new(&p, &g, &q);
+    let x = c57::recover_secret(&bob, &p, &q)?;
+    Ok(Outcome::new(bob.is_secret(&x), format!("{:x}", x)).with_queries(bob.queries()))
+}
+
+fn c58() -> Result<Outcome> {
+    let (p, g, q) = c58::challenge_params();
+    let bob = Bob::new(&p, &g, &q);
+    let x = c58::recover_secret(&bob, &p, &g, &q)?;
+    Ok(Outcome::new(bob.is_secret(&x), format!("{:x}", x)).with_queries(bob.queries()))
+}
+
+fn c59() -> Result<Outcome> {
+    let bob = c59::Bob::new(false);
+    let recovered = c59::recover_secret(&bob);
+    let validating = c59::Bob::new(true);
+    let solved = recovered.as_ref().is_ok_and(|x| bob.is_secret(x))
+        && matches!(
+            c59::recover_secret(&validating),
+            Err(CryptoError::OracleRefused(_))
+        );
+
+    let secret = recovered.map_or("none".to_owned(), |x| format!("{:x}", x));
+    Ok(Outcome::new(solved, secret).with_queries(bob.queries()))
+}
+
+fn c60() -> Result<Outcome> {
+    // Secret below 2^90, as the full kangaroo interval takes minutes
+    let secret_bound = BigUint::one() << 90;
+    let x = rand::thread_rng().gen_biguint_below(&secret_bound);
+    let bob = c60::Bob::with_secret(&x);
+    let recovered = c60::recover_secret(&bob, 1 << 19, &secret_bound)?;
+    Ok(
+        Outcome::new(bob.is_secret(&recovered), format!("{:x}", recovered))
+            .with_queries(bob.queries()),
+    )
+}
+
+fn c61() -> Result<Outcome> {
+    let msg = b"hi mom";
+    let ecdsa = ECDSA::challenge_59();
+    let (d, pub_key) = ecdsa.gen_key_pair();
+    let sig = ecdsa.sign(msg, &d);
+    let (forged, _, forged_key) = ecdsa_dsks(&ecdsa, msg, &sig, &pub_key);
+    let ecdsa_solved = forged_key != pub_key && forged.verify(msg, &sig, &forged_key);
+
+    let rsa = RSA::new(512);
+    let sig = rsa.sign(msg)?;
+    let forged = rsa_dsks(&rsa, msg, &sig)?;
+    let rsa_solved = forged.n != rsa.n && forged.verify(msg, &sig);
+
+    let secret = format!("n' = {:x}", forged.n);
+    Ok(Outcome::new(ecdsa_solved && rsa_solved, secret))
+}
+
+fn c62() -> Result<Outcome> {
+    let signer = Signer::new();
+    let sigs: Vec<_> = (0..22)
+        .map(|_| {
+            let msg: Vec<u8> = (0..16).map(|_| rand::thread_rng().gen()).collect();
+            let sig = signer.sign(&msg);
+            (msg, sig)
+        })
+        .collect();
+
+    let d = recover_key(&signer.ecdsa, &signer.pub_key(), &sigs);
+    let solved = d.as_ref().is_some_and(|d| signer.is_secret(d));
+    let secret = d.map_or("none".to_owned(), |d| format!("{:x}", d));
+    Ok(Outcome::new(solved, secret).with_queries(signer.queries()))
+}
+
+fn c63() -> Result<Outcome> {
+    let alice = c63::Alice::new();
+    let known = b"attack at dawn, from the north";
+    let mut messages = Vec::new();
+    for (msg, aad) in [
+        (&known[..], &b"header"[..]),
+        (b"retreat at dusk, to the south-west hills", b"other header"),
+    ] {
+        let (ciphertext, tag) = alice.encrypt(msg, aad)?;
+        messages.push(Authenticated {
+            aad: aad.to_vec(),
+            ciphertext,
+            tag,
+        });
+    }
+
+    // Known plaintext gives the keystream, right H the tag Alice accepts
+    let target = b"attack at noon, from the south";
+    let keystream = xor_bytes(&messages[0].ciphertext, known);
+    let ciphertext = xor_bytes(&keystream, target);
+    let forged = key_candidates(&messages[0], &messages[1])
+        .into_iter()
+        .find_map(|h| {
+            let tag = forge_tag(h, &messages[1], b"forged", &ciphertext);
+            alice.decrypt(&ciphertext, b"forged", &tag).ok()
+        });
+
+    let solved = forged.as_deref() == Some(&target[..]);
+    let secret = forged.map_or("none".to_owned(), |m| String::from_utf8_lossy(&m).into());
+    Ok(Outcome::new(solved, secret))
+}
+
+fn c64() -> Result<Outcome> {
+    // 16 bit tags, as 32 bit ones need about 2^16 forgery attempts (the
+    // ignored test_c64_32_bit_tags runs those)
+    let alice = c64::Alice::new(2);
+    let h = c64::recover_key(&alice, 9)?;
+    let queries = alice.queries();
+
+    // Recovered H gives the tag of any tampered ciphertext
+    let (iv, ciphertext, tag) = alice.encrypt(b"attack at dawn, from the north")?;
+    let forged = xor_bytes(&ciphertext, b"\0\0\0\0\0\0\0\0\0\0noon");
+    let delta = ghash(h, &[], &ciphertext) + ghash(h, &[], &forged);
+    let forged_tag = xor_bytes(&tag, &delta.to_block());
+    let solved = alice.verify(&iv, &forged, &forged_tag)?;
+
+    Ok(Outcome::new(solved, format!("H = {:032x}", h.0)).with_queries(queries))
+}
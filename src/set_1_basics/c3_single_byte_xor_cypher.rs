@@ -1,36 +1,42 @@
+use crate::error::Result;
 use crate::utils::letter_freq_test::calc_letter_freq_score;
 
-pub fn decipher_message(hex: &str) -> (String, f64) {
-    let cipher_bytes = hex::decode(hex).unwrap();
-    let mut key_byte: u8;
-
-    let mut message = String::new();
+pub fn decipher_bytes(cipher_bytes: &[u8]) -> (Vec<u8>, f64) {
+    let mut message = Vec::new();
     let mut best_score = f64::MIN;
-    for c in 0..=255 {
-        key_byte = c as u8;
-
+    for key_byte in 0..=255 {
         let msg_bytes: Vec<u8> = cipher_bytes.iter().map(|&b| b ^ key_byte).collect();
 
-        let msg = String::from_utf8_lossy(&msg_bytes);
-        let score = calc_letter_freq_score(&msg);
+        let score = calc_letter_freq_score(&String::from_utf8_lossy(&msg_bytes));
 
         if score > best_score {
             best_score = score;
-            message = String::from(msg);
+            message = msg_bytes;
         }
     }
 
     (message, best_score)
 }
 
+pub fn decipher_message(hex: &str) -> Result<(String, f64)> {
+    let (message, score) = decipher_bytes(&hex::decode(hex)?);
+    Ok((String::from_utf8_lossy(&message).into_owned(), score))
+}
+
 #[cfg(test)]
 mod test {
-    use super::decipher_message;
+    use super::*;
     #[test]
     fn test_c3() {
         let hex = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
         let message = "Cooking MC's like a pound of bacon";
-        let (output, _) = decipher_message(hex);
+        let (output, _) = decipher_message(hex).unwrap();
         assert_eq!(output, message);
+
+        // Plaintext with a non UTF-8 byte comes back as is
+        let mut plain = message.as_bytes().to_vec();
+        plain.push(0xff);
+        let cipher: Vec<u8> = plain.iter().map(|&b| b ^ 0x58).collect();
+        assert_eq!(decipher_bytes(&cipher).0, plain);
     }
 }
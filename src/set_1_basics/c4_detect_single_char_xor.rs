@@ -1,48 +1,47 @@
-use crate::utils::letter_freq_test::calc_letter_freq_score;
+use crate::error::Result;
+use crate::set_1_basics::c3_single_byte_xor_cypher::decipher_bytes;
 use hex;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
-pub fn detect_message(path: &str) -> String {
-    let mut message = String::new();
+pub fn detect_message_bytes(ciphertexts: &[Vec<u8>]) -> Vec<u8> {
+    let mut message = Vec::new();
     let mut best_score: f64 = f64::MIN;
 
-    let file = File::open(path).expect("Error reading file!");
-    let lines = BufReader::new(file).lines();
-
-    let mut line: String;
-    let mut key: u16;
-    for line_result in lines {
-        line = line_result.unwrap();
-        for c in 0..255 {
-            key = c as u16;
-
-            let msg_bytes: Vec<u16> = hex::decode(&line.trim())
-                .unwrap()
-                .iter()
-                .map(|&b| (b as u16) ^ key)
-                .collect();
-
-            let msg = String::from_utf16(&msg_bytes).unwrap();
-            let score = calc_letter_freq_score(&msg);
-
-            if score > best_score {
-                best_score = score;
-                message = String::from(msg);
-            }
+    for cipher_bytes in ciphertexts {
+        let (msg, score) = decipher_bytes(cipher_bytes);
+        if score > best_score {
+            best_score = score;
+            message = msg;
         }
     }
 
     message
 }
 
+pub fn detect_message(path: &str) -> Result<String> {
+    let file = File::open(path)?;
+    let ciphertexts = BufReader::new(file)
+        .lines()
+        .map(|line| Ok(hex::decode(line?.trim())?))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(String::from_utf8_lossy(&detect_message_bytes(&ciphertexts)).into_owned())
+}
+
 #[cfg(test)]
 mod test {
     use super::detect_message;
+    use crate::error::CryptoError;
     #[test]
     fn test_c4() {
-        let output = detect_message("files/set_1/4_strings.txt");
+        let output = detect_message("files/set_1/4_strings.txt").unwrap();
         let message = "Now that the party is jumping\n";
         assert_eq!(output, message);
+
+        assert!(matches!(
+            detect_message("files/set_1/missing.txt"),
+            Err(CryptoError::Io(_))
+        ));
     }
 }
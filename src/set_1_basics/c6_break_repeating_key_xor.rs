@@ -1,13 +1,12 @@
+use crate::error::Result;
 use crate::utils::hamming_distance::hamming_distance_bytes;
 use crate::utils::letter_freq_test::calc_letter_freq_score;
 use base64;
 use std::fs;
 
-fn read_bytes(path: &str) -> Vec<u8> {
-    let base64_s = fs::read_to_string(path)
-        .and_then(|res| Ok(res.replace("\n", "")))
-        .expect("Error reading file");
-    base64::decode(base64_s).unwrap()
+fn read_bytes(path: &str) -> Result<Vec<u8>> {
+    let base64_s = fs::read_to_string(path)?.replace("\n", "");
+    Ok(base64::decode(base64_s)?)
 }
 
 fn calc_avg_edit_dist(key_sz: usize, txt_bytes: &[u8]) -> f64 {
@@ -51,20 +50,19 @@ fn break_single_char_xor(xor_bytes: &[u8]) -> u8 {
     key
 }
 
-pub fn break_repeating_key_xor(path: &str) -> String {
-    let text_bytes = read_bytes(path);
-
+// Recovers the repeating key of the xored bytes
+pub fn break_repeating_key_xor_bytes(text_bytes: &[u8]) -> Vec<u8> {
     // (key size, edit dist) tuples vec
     let mut edit_dist: Vec<(usize, f64)> = Vec::new();
 
     for key_sz in 2..=40 {
-        let dist = calc_avg_edit_dist(key_sz, &text_bytes);
+        let dist = calc_avg_edit_dist(key_sz, text_bytes);
         edit_dist.push((key_sz, dist));
     }
 
     // Extract the shortest distance key
     edit_dist.sort_by(|x, y| y.1.partial_cmp(&x.1).unwrap());
-    let key_sz = edit_dist.pop().and_then(|x| Some(x.0)).unwrap();
+    let key_sz = edit_dist.pop().map(|x| x.0).unwrap();
 
     // Key bytes
     let mut key_bytes: Vec<u8> = Vec::new();
@@ -84,17 +82,20 @@ pub fn break_repeating_key_xor(path: &str) -> String {
         key_bytes.push(key_i);
     }
 
-    let key: String = key_bytes.iter().map(|&b| b as char).collect();
+    key_bytes
+}
 
-    key
+pub fn break_repeating_key_xor(path: &str) -> Result<String> {
+    let key_bytes = break_repeating_key_xor_bytes(&read_bytes(path)?);
+    Ok(String::from_utf8_lossy(&key_bytes).into_owned())
 }
 
 #[cfg(test)]
 mod test {
-    use super::break_repeating_key_xor;
+    use super::*;
     #[test]
     fn test_c6() {
-        let output = break_repeating_key_xor("files/set_1/6_base64.txt");
+        let output = break_repeating_key_xor("files/set_1/6_base64.txt").unwrap();
         let key = "Terminator X: Bring the noise";
         assert_eq!(output, key);
     }
@@ -1,39 +1,64 @@
+use crate::error::{CryptoError, Result};
 use aes::cipher::{generic_array::GenericArray, BlockDecrypt, NewBlockCipher};
 use aes::Aes128;
 use base64;
 use std::fs;
 
-fn read_bytes(path: &str) -> Vec<u8> {
-    let base64_s = fs::read_to_string(path)
-        .and_then(|res| Ok(res.replace("\n", "")))
-        .expect("Error reading file");
-    base64::decode(base64_s).unwrap()
+fn read_bytes(path: &str) -> Result<Vec<u8>> {
+    let base64_s = fs::read_to_string(path)?.replace("\n", "");
+    Ok(base64::decode(base64_s)?)
 }
 
-pub fn decrypt_message(path: &str, key_str: &str) -> String {
-    let base64_bytes = read_bytes(path);
-    let key = GenericArray::clone_from_slice(key_str.as_bytes());
+pub fn decrypt_bytes(cipher_bytes: &[u8], key_bytes: &[u8]) -> Result<Vec<u8>> {
+    if key_bytes.len() != 16 {
+        return Err(CryptoError::InvalidKeyLength(key_bytes.len()));
+    }
+    if !cipher_bytes.len().is_multiple_of(16) {
+        return Err(CryptoError::InvalidBlockLength(cipher_bytes.len() % 16));
+    }
+    let key = GenericArray::clone_from_slice(key_bytes);
 
     // Construct blocks of 16 byte size for AES-128
-    let mut blocks = Vec::new();
-    (0..base64_bytes.len()).step_by(16).for_each(|x| {
-        blocks.push(GenericArray::clone_from_slice(&base64_bytes[x..x + 16]));
-    });
+    let mut blocks: Vec<_> = cipher_bytes
+        .chunks_exact(16)
+        .map(GenericArray::clone_from_slice)
+        .collect();
 
     // Initialize cipher
     let cipher = Aes128::new(&key);
     cipher.decrypt_blocks(&mut blocks);
 
-    blocks.iter().flatten().map(|&x| x as char).collect()
+    Ok(blocks.into_iter().flatten().collect())
+}
+
+pub fn decrypt_message(path: &str, key_str: &str) -> Result<String> {
+    let message = decrypt_bytes(&read_bytes(path)?, key_str.as_bytes())?;
+    Ok(String::from_utf8_lossy(&message).into_owned())
 }
 
 #[cfg(test)]
 mod test {
-    use super::decrypt_message;
+    use super::*;
+    use crate::utils::aes::aes128_ecb_encrypt;
     #[test]
     fn test_c7() {
-        let output = decrypt_message("files/set_1/7_base64.txt", "YELLOW SUBMARINE");
+        let output = decrypt_message("files/set_1/7_base64.txt", "YELLOW SUBMARINE").unwrap();
         let message = "I'm back and I'm ringin' the bell \nA rockin' on the mike while the fly girls yell \nIn ecstasy in the back of me \nWell that's my DJ Deshay cuttin' all them Z's \nHittin' hard and the girlies goin' crazy \nVanilla's on the mike, man I'm not lazy. \n\nI'm lettin' my drug kick in \nIt controls my mouth and I begin \nTo just let it flow, let my concepts go \nMy posse's to the side yellin', Go Vanilla Go! \n\nSmooth 'cause that's the way I will be \nAnd if you don't give a damn, then \nWhy you starin' at me \nSo get off 'cause I control the stage \nThere's no dissin' allowed \nI'm in my own phase \nThe girlies sa y they love me and that is ok \nAnd I can dance better than any kid n' play \n\nStage 2 -- Yea the one ya' wanna listen to \nIt's off my head so let the beat play through \nSo I can funk it up and make it sound good \n1-2-3 Yo -- Knock on some wood \nFor good luck, I like my rhymes atrocious \nSupercalafragilisticexpialidocious \nI'm an effect and that you can bet \nI can take a fly girl and make her wet. \n\nI'm like Samson -- Samson to Delilah \nThere's no denyin', You can try to hang \nBut you'll keep tryin' to get my style \nOver and over, practice makes perfect \nBut not if you're a loafer. \n\nYou'll get nowhere, no place, no time, no girls \nSoon -- Oh my God, homebody, you probably eat \nSpaghetti with a spoon! Come on and say it! \n\nVIP. Vanilla Ice yep, yep, I'm comin' hard like a rhino \nIntoxicating so you stagger like a wino \nSo punks stop trying and girl stop cryin' \nVanilla Ice is sellin' and you people are buyin' \n'Cause why the freaks are jockin' like Crazy Glue \nMovin' and groovin' trying to sing along \nAll through the ghetto groovin' this here song \nNow you're amazed by the VIP posse. \n\nSteppin' so hard like a German Nazi \nStartled by the bases hittin' ground \nThere's no trippin' on mine, I'm just gettin' down \nSparkamatic, I'm hangin' tight like a fanatic \nYou trapped me once and I thought that \nYou might have it \nSo step down and lend me your ear \n'89 in my time! You, '90 is my year. \n\nYou're weakenin' fast, YO! and I can tell it \nYour body's gettin' hot, so, so I can smell it \nSo don't be mad and don't be sad \n'Cause the lyrics belong to ICE, You can call me Dad \nYou're pitchin' a fit, so step back and endure \nLet the witch doctor, Ice, do the dance to cure \nSo come up close and don't be square \nYou wanna battle me -- Anytime, anywhere \n\nYou thought that I was weak, Boy, you're dead wrong \nSo come on, everybody and sing this song \n\nSay -- Play that funky music Say, go white boy, go white boy go \nplay that funky music Go white boy, go white boy, go \nLay down and boogie and play that funky music till you die. \n\nPlay that funky music Come on, Come on, let me hear \nPlay that funky music white boy you say it, say it \nPlay that funky music A little louder now \nPlay that funky music, white boy Come on, Come on, Come on \nPlay that funky music";
         assert!(output.starts_with(message));
+
+        // Binary plaintext round trips, padding is left in place
+        let key = b"YELLOW SUBMARINE";
+        let plain: Vec<u8> = (0..=255).rev().collect();
+        let cipher = aes128_ecb_encrypt(&plain, key).unwrap();
+        assert_eq!(decrypt_bytes(&cipher, key).unwrap()[..256], plain[..]);
+
+        assert_eq!(
+            decrypt_bytes(&cipher[1..], key),
+            Err(CryptoError::InvalidBlockLength(15))
+        );
+        assert_eq!(
+            decrypt_bytes(&cipher, b"YELLOW"),
+            Err(CryptoError::InvalidKeyLength(6))
+        );
     }
 }
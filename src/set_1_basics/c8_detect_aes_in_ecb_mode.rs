@@ -1,20 +1,16 @@
+use crate::error::Result;
 use hex;
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
-pub fn detect_aes_ecb_encryption(path: &str) -> (usize, usize) {
-    let file = File::open(path).expect("Error reading file!");
-    let lines = BufReader::new(file).lines();
+// Index of the ciphertext most likely ECB encrypted & its repeated block count
+pub fn detect_aes_ecb_bytes(ciphertexts: &[Vec<u8>]) -> (usize, usize) {
     let mut i_line: usize = 0;
     let mut max_identical_blocks: usize = 0;
 
     let mut n_identical_blocks: usize;
-    for (i, line) in lines.enumerate() {
-        let hex = line.unwrap();
-        // Hex line to bytes vec
-        let bytes = hex::decode(hex).unwrap();
-
+    for (i, bytes) in ciphertexts.iter().enumerate() {
         // Divide bytes into 16 byte blocks (&[u8] blocks)
         let blocks: Vec<_> = bytes.chunks_exact(16).collect();
 
@@ -35,12 +31,23 @@ pub fn detect_aes_ecb_encryption(path: &str) -> (usize, usize) {
     (i_line, max_identical_blocks)
 }
 
+pub fn detect_aes_ecb_encryption(path: &str) -> Result<(usize, usize)> {
+    let file = File::open(path)?;
+    // Hex lines to bytes vecs
+    let ciphertexts = BufReader::new(file)
+        .lines()
+        .map(|line| Ok(hex::decode(line?)?))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(detect_aes_ecb_bytes(&ciphertexts))
+}
+
 #[cfg(test)]
 mod test {
     use super::detect_aes_ecb_encryption;
     #[test]
     fn test_c8() {
-        let (line_no, _) = detect_aes_ecb_encryption("files/set_1/8_hex.txt");
+        let (line_no, _) = detect_aes_ecb_encryption("files/set_1/8_hex.txt").unwrap();
         assert_eq!(line_no, 132);
     }
 }
@@ -1,44 +1,57 @@
-use crate::set_2_block_crypto::c9_implement_pkcs_padding::pad_pkcs7;
+use crate::error::{CryptoError, Result};
+use crate::set_2_block_crypto::c15_pkcs7_padding_validation::unpad_pkcs7_bytes;
+use crate::set_2_block_crypto::c9_implement_pkcs_padding::pad_pkcs7_bytes;
 use crate::utils::bitwise::xor_bytes;
 use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher};
 use aes::Aes128;
 
-pub fn aes_128_cbc_encrypt(message: &str, key_str: &str, iv_str: &str) -> String {
-    // Normalize message by pkcs7 padding
-    let padded_message = pad_pkcs7(message, 16);
-    let msg_bytes = padded_message.as_bytes();
-    let iv = iv_str.as_bytes().to_vec();
+fn check_key_iv(key_bytes: &[u8], iv: &[u8]) -> Result<Aes128> {
+    if key_bytes.len() != 16 {
+        return Err(CryptoError::InvalidKeyLength(key_bytes.len()));
+    }
+    if iv.len() != 16 {
+        return Err(CryptoError::InvalidBlockLength(iv.len()));
+    }
+    Ok(Aes128::new(GenericArray::from_slice(key_bytes)))
+}
 
-    let key = GenericArray::clone_from_slice(key_str.as_bytes());
-    let cipher = Aes128::new(&key);
+pub fn aes_128_cbc_encrypt_bytes(msg_bytes: &[u8], key_bytes: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
+    let cipher = check_key_iv(key_bytes, iv)?;
+
+    // Normalize message by pkcs7 padding
+    let padded_message = pad_pkcs7_bytes(msg_bytes, 16)?;
 
     let mut encrypted_blocks: Vec<Vec<u8>> = Vec::new();
-    (0..message.len()).step_by(16).for_each(|x| {
+    padded_message.chunks_exact(16).for_each(|msg_block| {
         // Take last encrypted block or IV for first block iteration
-        let last = encrypted_blocks.last().unwrap_or(&iv);
+        let last = encrypted_blocks.last().map_or(iv, |b| b.as_slice());
 
         // XOR last encrypted block with current msg block & encrypt result
-        let xor_block = xor_bytes(last, &msg_bytes[x..x + 16]);
+        let xor_block = xor_bytes(last, msg_block);
         let mut block = GenericArray::clone_from_slice(&xor_block);
         cipher.encrypt_block(&mut block);
 
         encrypted_blocks.push(block.into_iter().collect::<Vec<u8>>());
     });
 
-    hex::encode(encrypted_blocks.into_iter().flatten().collect::<Vec<u8>>())
+    Ok(encrypted_blocks.into_iter().flatten().collect())
 }
 
-pub fn aes_128_cbc_decrypt(cipher_hex: &str, key_str: &str, iv_str: &str) -> String {
-    let encrypted_bytes = hex::decode(cipher_hex).unwrap();
-    let key = GenericArray::clone_from_slice(key_str.as_bytes());
-    let iv = iv_str.as_bytes();
-    let cipher = Aes128::new(&key);
+pub fn aes_128_cbc_decrypt_bytes(
+    encrypted_bytes: &[u8],
+    key_bytes: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>> {
+    let cipher = check_key_iv(key_bytes, iv)?;
+    if encrypted_bytes.is_empty() || !encrypted_bytes.len().is_multiple_of(16) {
+        return Err(CryptoError::InvalidBlockLength(encrypted_bytes.len() % 16));
+    }
 
-    let mut decrypted_blocks: Vec<Vec<u8>> = Vec::new();
+    let mut decrypted_bytes: Vec<u8> = Vec::new();
     (0..encrypted_bytes.len()).step_by(16).for_each(|x| {
         // Take last of encrypted block or IV in case of first block iteration
         let last = if x == 0 {
-            &iv
+            iv
         } else {
             &encrypted_bytes[x - 16..x]
         };
@@ -46,34 +59,75 @@ pub fn aes_128_cbc_decrypt(cipher_hex: &str, key_str: &str, iv_str: &str) -> Str
         // Decrypt AES
         let mut block = GenericArray::clone_from_slice(&encrypted_bytes[x..x + 16]);
         cipher.decrypt_block(&mut block);
-        let decrypted_block = block.into_iter().collect::<Vec<u8>>();
 
         // XOR decrypted block with last encrypted block to undo xor during encryption
-        let xor_block = xor_bytes(last, &decrypted_block);
-        decrypted_blocks.push(xor_block);
+        decrypted_bytes.extend(xor_bytes(last, &block));
     });
 
-    // Get number of padding bytes applied during encryption & remove padding
-    let padding_byte = *decrypted_blocks.last().unwrap().last().unwrap() as usize;
-    decrypted_blocks
-        .into_iter()
-        .flatten()
-        .take(encrypted_bytes.len() - padding_byte)
-        .map(|x| x as char)
-        .collect::<String>()
+    // Remove padding applied during encryption
+    unpad_pkcs7_bytes(&decrypted_bytes, 16)
+}
+
+pub fn aes_128_cbc_encrypt(message: &str, key_str: &str, iv_str: &str) -> Result<String> {
+    let encrypted_bytes =
+        aes_128_cbc_encrypt_bytes(message.as_bytes(), key_str.as_bytes(), iv_str.as_bytes())?;
+    Ok(hex::encode(encrypted_bytes))
+}
+
+pub fn aes_128_cbc_decrypt(cipher_hex: &str, key_str: &str, iv_str: &str) -> Result<String> {
+    let decrypted_bytes = aes_128_cbc_decrypt_bytes(
+        &hex::decode(cipher_hex)?,
+        key_str.as_bytes(),
+        iv_str.as_bytes(),
+    )?;
+    Ok(String::from_utf8(decrypted_bytes)?)
 }
 
 #[cfg(test)]
 mod test {
-    use super::{aes_128_cbc_decrypt, aes_128_cbc_encrypt};
+    use super::*;
+    use crate::utils::aes::aes128_cbc_decrypt;
     #[test]
     fn test_c10() {
         let msg = "This is some secret message. Do not reveal. I mean really this is some secret..duh! Why would you want to reveal it anyway.";
         let key = "YELLOW SUBMARINE";
         let iv = "\x00".repeat(16);
 
-        let encrypted_msg_hex = aes_128_cbc_encrypt(msg, key, iv.as_str());
-        let decrypted_msg = aes_128_cbc_decrypt(encrypted_msg_hex.as_str(), key, iv.as_str());
+        let encrypted_msg_hex = aes_128_cbc_encrypt(msg, key, iv.as_str()).unwrap();
+        let decrypted_msg =
+            aes_128_cbc_decrypt(encrypted_msg_hex.as_str(), key, iv.as_str()).unwrap();
         assert_eq!(msg, decrypted_msg);
     }
+
+    #[test]
+    fn test_c10_bytes() {
+        let key = b"YELLOW SUBMARINE";
+        let iv = [0x80; 16];
+
+        // Binary message of whole blocks, so a full padding block is added
+        let msg: Vec<u8> = (0..=255).rev().collect();
+        let encrypted = aes_128_cbc_encrypt_bytes(&msg, key, &iv).unwrap();
+        assert_eq!(encrypted.len(), msg.len() + 16);
+        assert_eq!(aes128_cbc_decrypt(&encrypted, key, &iv).unwrap(), msg);
+        assert_eq!(
+            aes_128_cbc_decrypt_bytes(&encrypted, key, &iv).unwrap(),
+            msg
+        );
+
+        let mut tampered = encrypted.clone();
+        let n = tampered.len();
+        tampered[n - 17] ^= 0x01;
+        assert_eq!(
+            aes_128_cbc_decrypt_bytes(&tampered, key, &iv),
+            Err(CryptoError::BadPadding)
+        );
+        assert_eq!(
+            aes_128_cbc_decrypt_bytes(&encrypted[1..], key, &iv),
+            Err(CryptoError::InvalidBlockLength(15))
+        );
+        assert_eq!(
+            aes_128_cbc_encrypt_bytes(&msg, key, &iv[1..]),
+            Err(CryptoError::InvalidBlockLength(15))
+        );
+    }
 }
@@ -1,5 +1,7 @@
+use crate::error::Result;
 use crate::utils::aes::{aes128_cbc_encrypt, aes128_ecb_encrypt};
 use rand::Rng;
+use std::cell::Cell;
 use std::collections::HashSet;
 
 #[derive(Debug, PartialEq)]
@@ -12,7 +14,36 @@ fn gen_rand_bytes(n: usize) -> Vec<u8> {
     (0..n).map(|_| rand::random::<u8>()).collect()
 }
 
-fn random_encryption(msg: &[u8]) -> (Vec<u8>, EncryptionMode) {
+pub struct ModeOracle {
+    queries: Cell<usize>,
+}
+
+impl ModeOracle {
+    pub fn new() -> Self {
+        Self {
+            queries: Cell::new(0),
+        }
+    }
+
+    // Encrypts msg (with random bytes around it) under a random key, with
+    // ECB or CBC picked at random. Returns (ciphertext, mode picked).
+    pub fn encrypt(&self, msg: &[u8]) -> Result<(Vec<u8>, EncryptionMode)> {
+        self.queries.set(self.queries.get() + 1);
+        random_encryption(msg)
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl Default for ModeOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn random_encryption(msg: &[u8]) -> Result<(Vec<u8>, EncryptionMode)> {
     let use_ecb: bool = rand::random();
     let rand_key = gen_rand_bytes(16);
     let rand_iv = gen_rand_bytes(16);
@@ -33,40 +64,47 @@ fn random_encryption(msg: &[u8]) -> (Vec<u8>, EncryptionMode) {
     let cipherbytes;
     if use_ecb {
         mode = EncryptionMode::ECB;
-        cipherbytes = aes128_ecb_encrypt(&msg_bytes, &rand_key).unwrap();
+        cipherbytes = aes128_ecb_encrypt(&msg_bytes, &rand_key)?;
     } else {
-        cipherbytes = aes128_cbc_encrypt(&msg_bytes, &rand_key, &rand_iv).unwrap();
+        cipherbytes = aes128_cbc_encrypt(&msg_bytes, &rand_key, &rand_iv)?;
         mode = EncryptionMode::CBC;
     }
 
-    (cipherbytes, mode)
+    Ok((cipherbytes, mode))
 }
 
 // Returns (predicted_mode, actual_mode)
-pub fn detect_mode() -> (EncryptionMode, EncryptionMode) {
+pub fn detect_mode_with(oracle: &ModeOracle) -> Result<(EncryptionMode, EncryptionMode)> {
     let msg = "z".repeat(64);
 
-    let (cipherbytes, actual_mode) = random_encryption(msg.as_bytes());
+    let (cipherbytes, actual_mode) = oracle.encrypt(msg.as_bytes())?;
     let blocks: Vec<_> = cipherbytes.chunks_exact(16).collect();
     let unique_blocks: HashSet<_> = blocks.iter().cloned().collect();
 
     // No. of identical blocks detected
     let n_identical_blocks = blocks.len() - unique_blocks.len();
     if n_identical_blocks > 0 {
-        (EncryptionMode::ECB, actual_mode)
+        Ok((EncryptionMode::ECB, actual_mode))
     } else {
-        (EncryptionMode::CBC, actual_mode)
+        Ok((EncryptionMode::CBC, actual_mode))
     }
 }
 
+// Same as detect_mode_with, on a fresh oracle
+pub fn detect_mode() -> Result<(EncryptionMode, EncryptionMode)> {
+    detect_mode_with(&ModeOracle::new())
+}
+
 #[cfg(test)]
 mod test {
-    use super::detect_mode;
+    use super::{detect_mode_with, ModeOracle};
     #[test]
     fn test_c11() {
+        let oracle = ModeOracle::new();
         for _ in 0..100 {
-            let (predicted_mode, actual_mode) = detect_mode();
+            let (predicted_mode, actual_mode) = detect_mode_with(&oracle).unwrap();
             assert_eq!(predicted_mode, actual_mode);
         }
+        assert_eq!(oracle.queries(), 100);
     }
 }
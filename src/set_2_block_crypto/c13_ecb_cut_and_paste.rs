@@ -1,49 +1,84 @@
+use crate::error::{CryptoError, Result};
 use crate::utils::aes::{aes128_ecb_decrypt, aes128_ecb_encrypt};
+use std::cell::Cell;
 use std::collections::HashMap;
 
 const UNKNOWN_KEY: &[u8] = b"YELLOW SUBMARINE";
 
-pub fn parse_key_value(inp: &str) -> HashMap<String, String> {
+pub fn parse_key_value(inp: &str) -> Result<HashMap<String, String>> {
     inp.split('&')
-        .map(|kv| kv.split('=').collect::<Vec<_>>())
-        .map(|v| (String::from(v[0]), String::from(v[1])))
+        .map(|kv| match kv.split_once('=') {
+            Some((k, v)) => Ok((String::from(k), String::from(v))),
+            None => Err(CryptoError::InvalidEncoding(format!(
+                "no '=' in key value pair: {}",
+                kv
+            ))),
+        })
         .collect()
 }
 
-pub fn profile_for(email: &str) -> String {
+pub fn profile_for(email: &str) -> Result<String> {
     if email.chars().any(|c| c == '&' || c == '=') {
-        panic!("Metacharacters not allowed!");
+        Err(CryptoError::OracleRefused(
+            "metacharacters not allowed".to_owned(),
+        ))
     } else {
-        format!("email={}&uid=10&role=user", email)
+        Ok(format!("email={}&uid=10&role=user", email))
     }
 }
 
-pub fn encrypt_user_profile(profile: &str) -> Vec<u8> {
+pub fn encrypt_user_profile(profile: &str) -> Result<Vec<u8>> {
     // let rand_key: Vec<u8> = (0..16).map(|_| rand::random::<u8>()).collect();
-    aes128_ecb_encrypt(profile.as_bytes(), UNKNOWN_KEY).unwrap()
+    aes128_ecb_encrypt(profile.as_bytes(), UNKNOWN_KEY)
 }
 
-pub fn decode_user_profile(cipherbytes: &[u8]) -> HashMap<String, String> {
-    let bytes = aes128_ecb_decrypt(cipherbytes, UNKNOWN_KEY).unwrap();
+pub struct ProfileOracle {
+    queries: Cell<usize>,
+}
+
+impl ProfileOracle {
+    pub fn new() -> Self {
+        Self {
+            queries: Cell::new(0),
+        }
+    }
+
+    // Encrypted profile of a new user with the given email
+    pub fn encrypt_profile_for(&self, email: &str) -> Result<Vec<u8>> {
+        self.queries.set(self.queries.get() + 1);
+        encrypt_user_profile(&profile_for(email)?)
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl Default for ProfileOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn decode_user_profile(cipherbytes: &[u8]) -> Result<HashMap<String, String>> {
+    let bytes = aes128_ecb_decrypt(cipherbytes, UNKNOWN_KEY)?;
     parse_key_value(&String::from_utf8_lossy(&bytes))
 }
 
 // Attacker
-pub fn create_admin_profile() -> (Vec<u8>, String) {
+pub fn create_admin_profile_with(oracle: &ProfileOracle) -> Result<(Vec<u8>, String)> {
     // Chosen email such that last block doesn't contain
     // meta-chars (= and &) in profile
     let chosen_email = "attck@bar.com";
 
-    // Yields "email=attck@bar.com&uid=10&role=user"
-    // With plain-text blocks -
+    // Profile "email=attck@bar.com&uid=10&role=user" has plain-text
+    // blocks -
     // block 1: email=attck@bar.
     // block 2: com&uid=10&role=
     // block 3: user\x0c\x0c.....(len 16) (with pkcs#7)
-    let profile = profile_for(chosen_email);
-
     // Encrypted blocks corresponding to 3 blocks above. Have to
     // somehow replace last block corresponding to "admin\x0b\x0b..." (len 16)
-    let cipherbytes = encrypt_user_profile(&profile);
+    let cipherbytes = oracle.encrypt_profile_for(chosen_email)?;
 
     // No. of padding required if role was admin in target profile
     // which is "email=attck@bar.com&uid=10&role=admin"
@@ -60,12 +95,10 @@ pub fn create_admin_profile() -> (Vec<u8>, String) {
     // target_last_block - 'admin\x0c\x0c\x0c.....';
     let attack_email = format!("attck@bar.{}", target_last_block);
 
-    // Yields "email=attck@bar.admin\x0c\x0c\x0c....&uid=10&role=user"
-    // With "admin\x0c\x0c..." as second block whose encryption will be
+    // Profile "email=attck@bar.admin\x0c\x0c\x0c....&uid=10&role=user"
+    // has "admin\x0c\x0c..." as second block whose encryption will be
     // retrieved
-    let attack_profile = profile_for(&attack_email);
-
-    let attack_cipherbytes = encrypt_user_profile(&attack_profile);
+    let attack_cipherbytes = oracle.encrypt_profile_for(&attack_email)?;
 
     // Retrieve second block which corresponds to cipher encryption of
     // target_last_block
@@ -86,17 +119,27 @@ pub fn create_admin_profile() -> (Vec<u8>, String) {
         .cloned()
         .collect::<Vec<u8>>();
 
-    (target_profile, chosen_email.to_owned())
+    Ok((target_profile, chosen_email.to_owned()))
+}
+
+// Same as create_admin_profile_with, on a fresh oracle
+pub fn create_admin_profile() -> Result<(Vec<u8>, String)> {
+    create_admin_profile_with(&ProfileOracle::new())
 }
 
 #[cfg(test)]
 mod test {
-    use super::{create_admin_profile, decode_user_profile};
+    use super::{
+        create_admin_profile_with, decode_user_profile, parse_key_value, profile_for, ProfileOracle,
+    };
+    use crate::error::CryptoError;
     use std::collections::HashMap;
     #[test]
     fn test_c13() {
-        let (out, admin_email) = create_admin_profile();
-        let profile = decode_user_profile(&out);
+        let oracle = ProfileOracle::new();
+        let (out, admin_email) = create_admin_profile_with(&oracle).unwrap();
+        assert_eq!(oracle.queries(), 2);
+        let profile = decode_user_profile(&out).unwrap();
 
         let mut target = HashMap::new();
         target.insert("email".to_owned(), admin_email);
@@ -106,4 +149,17 @@ mod test {
         assert_eq!(profile.get("uid"), target.get("uid"));
         assert_eq!(profile.get("role"), target.get("role"));
     }
+
+    #[test]
+    fn test_c13_errors() {
+        assert!(matches!(
+            profile_for("foo@bar.com&role=admin"),
+            Err(CryptoError::OracleRefused(_))
+        ));
+        assert!(matches!(
+            parse_key_value("foo=bar&baz"),
+            Err(CryptoError::InvalidEncoding(_))
+        ));
+        assert_eq!(decode_user_profile(&[0; 5]), Err(CryptoError::BadPadding));
+    }
 }
@@ -1,50 +1,81 @@
+use crate::error::{CryptoError, Result};
 use crate::utils::aes::aes128_ecb_encrypt;
 use base64;
+use std::cell::Cell;
 use std::collections::HashMap;
 
 const UNKNOWN_KEY: &[u8] = b"YELLOW SUBMARINE";
 const UNKNOWN_SECRET: &str = "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkgaGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBqdXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUgYnkK";
 const UNKNOWN_PREFIX: &[u8] = b"r4nd0m length of s0m3 r4and0m pr3f1x";
 
-fn unknown_key_encrypt(msg: &[u8]) -> Vec<u8> {
-    let append_bytes = base64::decode(UNKNOWN_SECRET).unwrap();
-    let buffer: Vec<u8> = UNKNOWN_PREFIX
-        .iter()
-        .chain(msg.iter())
-        .chain(append_bytes.iter())
-        .cloned()
-        .collect();
-    aes128_ecb_encrypt(&buffer, UNKNOWN_KEY).unwrap()
+pub struct EcbOracle {
+    queries: Cell<usize>,
 }
 
-pub fn detect_block_size() -> usize {
+impl EcbOracle {
+    pub fn new() -> Self {
+        Self {
+            queries: Cell::new(0),
+        }
+    }
+
+    // Encrypts unknown prefix || msg || unknown secret under the unknown key
+    pub fn encrypt(&self, msg: &[u8]) -> Result<Vec<u8>> {
+        self.queries.set(self.queries.get() + 1);
+        let append_bytes = base64::decode(UNKNOWN_SECRET)?;
+        let buffer: Vec<u8> = UNKNOWN_PREFIX
+            .iter()
+            .chain(msg.iter())
+            .chain(append_bytes.iter())
+            .cloned()
+            .collect();
+        aes128_ecb_encrypt(&buffer, UNKNOWN_KEY)
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl Default for EcbOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn detect_block_size_with(oracle: &EcbOracle) -> Result<usize> {
     let block_size;
-    let a = 'A' as u8;
+    let a = b'A';
 
     let mut inp = vec![a];
-    let last_len = unknown_key_encrypt(&[a; 1]).len();
+    let last_len = oracle.encrypt(&[a; 1])?.len();
 
     loop {
         inp.push(a);
-        let cipherbytes = unknown_key_encrypt(&inp);
+        let cipherbytes = oracle.encrypt(&inp)?;
         if cipherbytes.len() != last_len {
             block_size = cipherbytes.len() - last_len;
             break;
         }
     }
 
-    block_size
+    Ok(block_size)
+}
+
+// Same as detect_block_size_with, on a fresh oracle
+pub fn detect_block_size() -> Result<usize> {
+    detect_block_size_with(&EcbOracle::new())
 }
 
-pub fn detect_prefix_len(block_size: usize) -> usize {
+pub fn detect_prefix_len_with(oracle: &EcbOracle, block_size: usize) -> Result<usize> {
     let mut len: usize = 0;
 
-    let a = 'a' as u8;
+    let a = b'a';
     let mut inp = vec![a; block_size * 2];
 
     // Detect index of ECB repeated blocks to calculate prefix length
     for i in 0..block_size {
-        let cipherbytes = unknown_key_encrypt(&inp);
+        let cipherbytes = oracle.encrypt(&inp)?;
         let blocks = cipherbytes.chunks_exact(block_size).collect::<Vec<&[u8]>>();
         let repeated_block_idx = (0..blocks.len() - 1).find(|i| blocks[*i] == blocks[i + 1]);
 
@@ -56,12 +87,21 @@ pub fn detect_prefix_len(block_size: usize) -> usize {
         inp.push(a);
     }
 
-    len
+    Ok(len)
 }
 
-pub fn decrypt_unknown(block_size: usize, prefix_len: usize) -> String {
+// Same as detect_prefix_len_with, on a fresh oracle
+pub fn detect_prefix_len(block_size: usize) -> Result<usize> {
+    detect_prefix_len_with(&EcbOracle::new(), block_size)
+}
+
+pub fn decrypt_unknown_with(
+    oracle: &EcbOracle,
+    block_size: usize,
+    prefix_len: usize,
+) -> Result<String> {
     let mut unknown_bytes: Vec<u8> = Vec::new();
-    let a = 'A' as u8;
+    let a = b'A';
 
     // No. of extra bytes required to have even blocks
     // containing the prefix
@@ -76,8 +116,7 @@ pub fn decrypt_unknown(block_size: usize, prefix_len: usize) -> String {
         let input = if i_block == 0 {
             vec![a; block_size - 1 - unknown_bytes.len() + n_extra_prefix_bytes]
         } else {
-            std::iter::repeat(&a)
-                .take(n_extra_prefix_bytes)
+            std::iter::repeat_n(&a, n_extra_prefix_bytes)
                 .chain(
                     unknown_bytes
                         .iter()
@@ -97,24 +136,25 @@ pub fn decrypt_unknown(block_size: usize, prefix_len: usize) -> String {
                 .cloned()
                 .collect();
 
-            let out_block = unknown_key_encrypt(&byte_short_inp)
+            let out_block = oracle
+                .encrypt(&byte_short_inp)?
                 .iter()
                 .skip(i_block * block_size + n_prefix_blocks * block_size)
                 .take(block_size)
                 .cloned()
                 .collect::<Vec<_>>();
 
-            codebook.insert(out_block, n as u8);
+            codebook.insert(out_block, n);
         }
 
-        let cipherbytes = unknown_key_encrypt(&input);
+        let cipherbytes = oracle.encrypt(&input)?;
 
         let matched_byte = codebook
             .get(
                 &cipherbytes[i_block * block_size + n_prefix_blocks * block_size
                     ..(i_block * block_size + n_prefix_blocks * block_size) + block_size],
             )
-            .unwrap();
+            .ok_or_else(|| CryptoError::AttackFailed("no codebook entry matched".to_owned()))?;
 
         unknown_bytes.push(*matched_byte);
         codebook.clear();
@@ -123,29 +163,36 @@ pub fn decrypt_unknown(block_size: usize, prefix_len: usize) -> String {
             break;
         }
 
-        if unknown_bytes.len() % block_size == 0 {
+        if unknown_bytes.len().is_multiple_of(block_size) {
             i_block += 1;
         }
     }
 
-    String::from_utf8_lossy(&unknown_bytes).to_string()
+    Ok(String::from_utf8_lossy(&unknown_bytes).to_string())
+}
+
+// Same as decrypt_unknown_with, on a fresh oracle
+pub fn decrypt_unknown(block_size: usize, prefix_len: usize) -> Result<String> {
+    decrypt_unknown_with(&EcbOracle::new(), block_size, prefix_len)
 }
 
 #[cfg(test)]
 mod test {
     use super::{
-        decrypt_unknown, detect_block_size, detect_prefix_len, UNKNOWN_KEY, UNKNOWN_PREFIX,
+        decrypt_unknown_with, detect_block_size_with, detect_prefix_len_with, EcbOracle,
+        UNKNOWN_KEY, UNKNOWN_PREFIX,
     };
     #[test]
     fn test_c14() {
-        let block_size = detect_block_size();
+        let oracle = EcbOracle::new();
+        let block_size = detect_block_size_with(&oracle).unwrap();
         assert_eq!(block_size, UNKNOWN_KEY.len());
 
-        let prefix_len = detect_prefix_len(block_size);
+        let prefix_len = detect_prefix_len_with(&oracle, block_size).unwrap();
         assert_eq!(prefix_len, UNKNOWN_PREFIX.len());
 
         let secret = "Rollin' in my 5.0\nWith my rag-top down so my hair can blow\nThe girlies on standby waving just to say hi\nDid you stop? No, I just drove by\n\u{1}";
-        let out = decrypt_unknown(block_size, prefix_len);
+        let out = decrypt_unknown_with(&oracle, block_size, prefix_len).unwrap();
         assert_eq!(out, secret);
     }
 }
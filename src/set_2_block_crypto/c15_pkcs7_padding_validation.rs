@@ -1,3 +1,6 @@
+use crate::error::{CryptoError, Result};
+use crate::set_2_block_crypto::c9_implement_pkcs_padding::check_block_size;
+
 pub fn validate_pkcs7_padding(inp: &str) -> bool {
     let padding_byte = inp.chars().last().unwrap() as u8;
     inp.chars()
@@ -6,13 +9,41 @@ pub fn validate_pkcs7_padding(inp: &str) -> bool {
         .all(|x| x == padding_byte as char)
 }
 
+pub fn unpad_pkcs7_bytes(padded: &[u8], block_size: usize) -> Result<Vec<u8>> {
+    check_block_size(block_size)?;
+    let padding_byte = *padded.last().ok_or(CryptoError::BadPadding)?;
+    let padding_size = padding_byte as usize;
+    if padding_size == 0
+        || padding_size > block_size
+        || !padded.ends_with(&vec![padding_byte; padding_size])
+    {
+        return Err(CryptoError::BadPadding);
+    }
+    Ok(padded[..padded.len() - padding_size].to_vec())
+}
+
 #[cfg(test)]
 mod test {
-    use super::validate_pkcs7_padding;
+    use super::*;
     #[test]
     fn test_c15() {
         assert!(validate_pkcs7_padding("ICE ICE BABY\x04\x04\x04\x04"));
         assert!(!validate_pkcs7_padding("ICE ICE BABY\x05\x05\x05\x05"));
         assert!(!validate_pkcs7_padding("ICE ICE BABY\x01\x02\x03\x04"));
+
+        assert_eq!(unpad_pkcs7_bytes(&[0xff, 0x02, 0x02], 16), Ok(vec![0xff]));
+        assert_eq!(
+            unpad_pkcs7_bytes(&[0xff, 0x00], 16),
+            Err(CryptoError::BadPadding)
+        );
+        assert_eq!(
+            unpad_pkcs7_bytes(&[0x11; 17], 16),
+            Err(CryptoError::BadPadding)
+        );
+        assert_eq!(unpad_pkcs7_bytes(&[], 16), Err(CryptoError::BadPadding));
+        assert_eq!(
+            unpad_pkcs7_bytes(&[0x01], 0),
+            Err(CryptoError::InvalidBlockLength(0))
+        );
     }
 }
@@ -1,35 +1,68 @@
+use crate::error::{CryptoError, Result};
 use crate::utils::aes::{aes128_cbc_decrypt, aes128_cbc_encrypt};
 use crate::utils::bitwise::xor_bytes;
+use std::cell::Cell;
 
 const UNKNOWN_KEY: &[u8] = b"YELLOW SUBMARINE";
 const UNKNOWN_IV: &[u8] = b"PURPLE SUBMARINE";
 
-pub fn encrypt_data(data: &str) -> Vec<u8> {
-    if data.chars().any(|c| c == ';' || c == '=') {
-        panic!("Included prohibited characters!")
-    } else {
-        let inp = format!(
-            "comment1=cooking%20MCs;userdata={};comment2=%20like%20a%20pound%20of%20bacon",
-            data
-        );
-        aes128_cbc_encrypt(inp.as_bytes(), UNKNOWN_KEY, UNKNOWN_IV).unwrap()
+pub struct UserdataOracle {
+    queries: Cell<usize>,
+}
+
+impl UserdataOracle {
+    pub fn new() -> Self {
+        Self {
+            queries: Cell::new(0),
+        }
+    }
+
+    // Encrypts the comment string with data quoted in as userdata
+    pub fn encrypt_data(&self, data: &str) -> Result<Vec<u8>> {
+        self.queries.set(self.queries.get() + 1);
+        if data.chars().any(|c| c == ';' || c == '=') {
+            Err(CryptoError::OracleRefused(
+                "included prohibited characters".to_owned(),
+            ))
+        } else {
+            let inp = format!(
+                "comment1=cooking%20MCs;userdata={};comment2=%20like%20a%20pound%20of%20bacon",
+                data
+            );
+            aes128_cbc_encrypt(inp.as_bytes(), UNKNOWN_KEY, UNKNOWN_IV)
+        }
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl Default for UserdataOracle {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-pub fn decrypt_and_find_admin(cipherbytes: &[u8]) -> Option<usize> {
-    let msg_bytes = aes128_cbc_decrypt(cipherbytes, UNKNOWN_KEY, UNKNOWN_IV).unwrap();
+// Same as UserdataOracle::encrypt_data, on a fresh oracle
+pub fn encrypt_data(data: &str) -> Result<Vec<u8>> {
+    UserdataOracle::new().encrypt_data(data)
+}
+
+pub fn decrypt_and_find_admin(cipherbytes: &[u8]) -> Result<Option<usize>> {
+    let msg_bytes = aes128_cbc_decrypt(cipherbytes, UNKNOWN_KEY, UNKNOWN_IV)?;
     let msg = String::from_utf8_lossy(&msg_bytes);
-    msg.find(";admin=true;")
+    Ok(msg.find(";admin=true;"))
 }
 
-pub fn bit_flip_attack(block_size: usize) -> Vec<u8> {
+pub fn bit_flip_attack_with(oracle: &UserdataOracle, block_size: usize) -> Result<Vec<u8>> {
     // Index of target block to flip bits of (assumed prepend text is known )
     let target_block_idx = 1;
 
     // This should covert to AAAAA;admin=true after attack
     let trojan_plaintext_block = "A".repeat(block_size);
 
-    let mut cipherbytes = encrypt_data(&trojan_plaintext_block);
+    let mut cipherbytes = oracle.encrypt_data(&trojan_plaintext_block)?;
 
     let target_cipher_block = cipherbytes
         .iter()
@@ -53,16 +86,29 @@ pub fn bit_flip_attack(block_size: usize) -> Vec<u8> {
         replacement,
     );
 
-    cipherbytes
+    Ok(cipherbytes)
+}
+
+// Same as bit_flip_attack_with, on a fresh oracle
+pub fn bit_flip_attack(block_size: usize) -> Result<Vec<u8>> {
+    bit_flip_attack_with(&UserdataOracle::new(), block_size)
 }
 
 #[cfg(test)]
 mod test {
-    use super::{bit_flip_attack, decrypt_and_find_admin};
+    use super::{bit_flip_attack_with, decrypt_and_find_admin, encrypt_data, UserdataOracle};
+    use crate::error::CryptoError;
     #[test]
     fn test_c16() {
-        let cipherbytes = bit_flip_attack(16);
-        let idx = decrypt_and_find_admin(&cipherbytes);
+        let oracle = UserdataOracle::new();
+        let cipherbytes = bit_flip_attack_with(&oracle, 16).unwrap();
+        let idx = decrypt_and_find_admin(&cipherbytes).unwrap();
         assert!(idx.is_some());
+        assert_eq!(oracle.queries(), 1);
+
+        assert!(matches!(
+            encrypt_data(";admin=true;"),
+            Err(CryptoError::OracleRefused(_))
+        ));
     }
 }
@@ -1,25 +1,61 @@
-pub fn pad_pkcs7(message: &str, block_size: usize) -> String {
+use crate::error::{CryptoError, Result};
+
+// Every padding byte holds the padding size, so blocks are 1 to 255 bytes
+pub fn check_block_size(block_size: usize) -> Result<()> {
+    if !(1..=255).contains(&block_size) {
+        return Err(CryptoError::InvalidBlockLength(block_size));
+    }
+    Ok(())
+}
+
+// Padding bytes of 128 and up aren't valid UTF-8 on their own, so block
+// sizes that large give an encoding error
+pub fn pad_pkcs7(message: &str, block_size: usize) -> Result<String> {
+    Ok(String::from_utf8(pad_pkcs7_bytes(
+        message.as_bytes(),
+        block_size,
+    )?)?)
+}
+
+pub fn pad_pkcs7_bytes(message: &[u8], block_size: usize) -> Result<Vec<u8>> {
+    check_block_size(block_size)?;
     let padding_size = block_size - message.len() % block_size;
-    let padding_char = padding_size as u8 as char;
-    let padding: String = (0..padding_size).map(|_| padding_char).collect();
-    format!("{}{}", message, padding)
+    let mut padded = message.to_vec();
+    padded.resize(message.len() + padding_size, padding_size as u8);
+    Ok(padded)
 }
 
 #[cfg(test)]
 mod test {
-    use super::pad_pkcs7;
+    use super::*;
     #[test]
     fn test_c9() {
-        let output1 = pad_pkcs7("YELLOW SUBMARINE", 20);
+        let output1 = pad_pkcs7("YELLOW SUBMARINE", 20).unwrap();
         let padded1 = "YELLOW SUBMARINE\x04\x04\x04\x04";
         assert_eq!(output1, padded1);
 
-        let output2 = pad_pkcs7("YELLOW SUBMARINEYELLOW SUBMARINE", 16);
+        let output2 = pad_pkcs7("YELLOW SUBMARINEYELLOW SUBMARINE", 16).unwrap();
         let padded2 = format!(
             "{}{}",
             "YELLOW SUBMARINEYELLOW SUBMARINE",
             "\x10".repeat(16)
         );
         assert_eq!(output2, padded2);
+
+        assert_eq!(
+            pad_pkcs7_bytes(&[0xff; 3], 4).unwrap(),
+            [0xff, 0xff, 0xff, 0x01]
+        );
+        assert_eq!(pad_pkcs7_bytes(b"YELLOW", 255).unwrap()[6..], [249; 249]);
+        for block_size in [0, 256, 300] {
+            assert_eq!(
+                pad_pkcs7_bytes(b"YELLOW", block_size),
+                Err(CryptoError::InvalidBlockLength(block_size))
+            );
+        }
+        assert!(matches!(
+            pad_pkcs7("YELLOW SUBMARINE", 200),
+            Err(CryptoError::InvalidEncoding(_))
+        ));
     }
 }
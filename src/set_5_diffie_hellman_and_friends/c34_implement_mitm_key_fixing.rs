@@ -1,3 +1,4 @@
+use crate::error::Result;
 use crate::utils::aes::{aes128_cbc_decrypt, aes128_cbc_encrypt};
 use crate::utils::diffie_hellman::DH;
 use crate::utils::sha::sha1_hash;
@@ -29,10 +30,16 @@ impl Comms {
         self.iv_b = iv_b.to_vec();
     }
 
-    pub fn encrypt_msg(&self, msg: &[u8], pk: &[u8], pub_key_other: &[u8], iv: &[u8]) -> Vec<u8> {
+    pub fn encrypt_msg(
+        &self,
+        msg: &[u8],
+        pk: &[u8],
+        pub_key_other: &[u8],
+        iv: &[u8],
+    ) -> Result<Vec<u8>> {
         let sk = self.dh.gen_session_key(pub_key_other, pk);
         let key = sha1_hash(&sk).iter().take(16).cloned().collect::<Vec<u8>>();
-        aes128_cbc_encrypt(msg, &key, &iv).unwrap()
+        aes128_cbc_encrypt(msg, &key, iv)
     }
 
     pub fn decrypt_msg(
@@ -41,10 +48,10 @@ impl Comms {
         pk: &[u8],
         pub_key_other: &[u8],
         iv: &[u8],
-    ) -> Vec<u8> {
+    ) -> Result<Vec<u8>> {
         let sk = self.dh.gen_session_key(pub_key_other, pk);
         let key = sha1_hash(&sk).iter().take(16).cloned().collect::<Vec<u8>>();
-        aes128_cbc_decrypt(ciphertext, &key, iv).unwrap()
+        aes128_cbc_decrypt(ciphertext, &key, iv)
     }
 }
 
@@ -56,7 +63,7 @@ pub fn simulate_crack(
     iv_a: &[u8],
     iv_b: &[u8],
     msg: &[u8],
-) -> (Vec<u8>, Vec<u8>) {
+) -> Result<(Vec<u8>, Vec<u8>)> {
     let dh = DH::new(p, g);
 
     let pub_key_a = dh.gen_pub_key(pk_a);
@@ -86,21 +93,21 @@ pub fn simulate_crack(
     comm.pub_key_b = comm.dh.p.clone();
 
     // A encrypts message and sends ciphertext + A's iv to B
-    let cipher_a = comm.encrypt_msg(msg, pk_a, &comm.pub_key_b, &comm.iv_a);
+    let cipher_a = comm.encrypt_msg(msg, pk_a, &comm.pub_key_b, &comm.iv_a)?;
 
     // M is able intercept the A's encrypted message
-    let cracked_msg_a = aes128_cbc_decrypt(&cipher_a, &cracked_key, &comm.iv_a).unwrap();
+    let cracked_msg_a = aes128_cbc_decrypt(&cipher_a, &cracked_key, &comm.iv_a)?;
 
     // B is able to decrypt the message
-    let msg_received = comm.decrypt_msg(&cipher_a, pk_b, &comm.pub_key_a, &comm.iv_a);
+    let msg_received = comm.decrypt_msg(&cipher_a, pk_b, &comm.pub_key_a, &comm.iv_a)?;
 
     // B encrypts the same message and sends to A
-    let cipher_b = comm.encrypt_msg(&msg_received, pk_b, &comm.pub_key_a, &comm.iv_b);
+    let cipher_b = comm.encrypt_msg(&msg_received, pk_b, &comm.pub_key_a, &comm.iv_b)?;
 
     // M is also able intercept the B's encrypted message
-    let cracked_msg_b = aes128_cbc_decrypt(&cipher_b, &cracked_key, &comm.iv_b).unwrap();
+    let cracked_msg_b = aes128_cbc_decrypt(&cipher_b, &cracked_key, &comm.iv_b)?;
 
-    (cracked_msg_a, cracked_msg_b)
+    Ok((cracked_msg_a, cracked_msg_b))
 }
 
 #[cfg(test)]
@@ -131,23 +138,32 @@ mod test {
 
         // A encrypts message and sends ciphertext + A's iv to B
         let msg = b"hello B!";
-        let cipher_a = comm.encrypt_msg(msg, PK_A, &comm.pub_key_b, &comm.iv_a);
+        let cipher_a = comm
+            .encrypt_msg(msg, PK_A, &comm.pub_key_b, &comm.iv_a)
+            .unwrap();
 
         // B is able to decrypt the message
-        let msg_received = comm.decrypt_msg(&cipher_a, PK_B, &comm.pub_key_a, &comm.iv_a);
+        let msg_received = comm
+            .decrypt_msg(&cipher_a, PK_B, &comm.pub_key_a, &comm.iv_a)
+            .unwrap();
         // To prove legitimacy B encrypts the message again with its own iv
         // and sends the ciphertext + B's iv to A
-        let cipher_b = comm.encrypt_msg(&msg_received, PK_B, &comm.pub_key_a, &comm.iv_b);
+        let cipher_b = comm
+            .encrypt_msg(&msg_received, PK_B, &comm.pub_key_a, &comm.iv_b)
+            .unwrap();
 
         // To confirm authenticity A is able to decrypt the same message
-        let msg_confirm = comm.decrypt_msg(&cipher_b, PK_A, &comm.pub_key_b, &comm.iv_b);
+        let msg_confirm = comm
+            .decrypt_msg(&cipher_b, PK_A, &comm.pub_key_b, &comm.iv_b)
+            .unwrap();
         assert_eq!(msg, msg_confirm.as_slice());
     }
 
     #[test]
     fn test_c34() {
         let msg = b"hello B!";
-        let (cracked_msg_a, cracked_msg_b) = simulate_crack(P, G, PK_A, PK_B, IV_A, IV_B, msg);
+        let (cracked_msg_a, cracked_msg_b) =
+            simulate_crack(P, G, PK_A, PK_B, IV_A, IV_B, msg).unwrap();
         assert_eq!(msg, cracked_msg_a.as_slice());
         assert_eq!(msg, cracked_msg_b.as_slice());
     }
@@ -1,3 +1,4 @@
+use crate::error::{CryptoError, Result};
 use crate::utils::aes::{aes128_cbc_decrypt, aes128_cbc_encrypt};
 use crate::utils::diffie_hellman::DH;
 use crate::utils::sha::sha1_hash;
@@ -17,40 +18,37 @@ impl Comms {
     }
 
     // second actor (B) acknowledges with received p & g
-    pub fn acknowledge(&mut self, p: &[u8], g: &[u8]) -> Result<(), String> {
+    pub fn acknowledge(&mut self, p: &[u8], g: &[u8]) -> Result<()> {
         if p.iter().eq(self.dh.p.iter()) && g.iter().eq(self.dh.g.iter()) {
             self.ack = true;
             Ok(())
         } else {
-            Err("acknowledgement failed!".to_string())
+            Err(CryptoError::ProtocolState(
+                "acknowledgement failed".to_owned(),
+            ))
         }
     }
 
     // Participate generating a public key
-    pub fn participate(&mut self, pk: &[u8], iv: &[u8]) -> Vec<u8> {
+    pub fn participate(&mut self, pk: &[u8], _iv: &[u8]) -> Result<Vec<u8>> {
         if !self.ack {
-            panic!("communication not acknowledged yet!");
+            return Err(CryptoError::ProtocolState(
+                "communication not acknowledged yet".to_owned(),
+            ));
         }
-        let pub_key = self.dh.gen_pub_key(pk);
-        pub_key
+        Ok(self.dh.gen_pub_key(pk))
     }
 
-    pub fn encrypt_msg(&self, msg: &[u8], pk: &[u8], pub_key_other: &[u8], iv: &[u8]) -> Vec<u8> {
-        let sk = self.dh.gen_session_key(pub_key_other, pk);
-        let key = sha1_hash(&sk).iter().take(16).cloned().collect::<Vec<u8>>();
-        aes128_cbc_encrypt(msg, &key, &iv).unwrap()
-    }
-
-    pub fn decrypt_msg(
+    pub fn encrypt_msg(
         &self,
-        ciphertext: &[u8],
+        msg: &[u8],
         pk: &[u8],
         pub_key_other: &[u8],
         iv: &[u8],
-    ) -> Vec<u8> {
+    ) -> Result<Vec<u8>> {
         let sk = self.dh.gen_session_key(pub_key_other, pk);
         let key = sha1_hash(&sk).iter().take(16).cloned().collect::<Vec<u8>>();
-        aes128_cbc_decrypt(ciphertext, &key, iv).unwrap()
+        aes128_cbc_encrypt(msg, &key, iv)
     }
 }
 
@@ -62,22 +60,21 @@ pub fn simulate_crack(
     iv_a: &[u8],
     iv_b: &[u8],
     msg: &[u8],
-) -> (Vec<u8>, Vec<u8>) {
+) -> Result<(Vec<u8>, Vec<u8>)> {
     // A opens a communication channel f(p, g)
     let (p, g, mut comm) = Comms::init(p, g);
 
     // B acknowledges with received p & g
-    let res = comm.acknowledge(&p, &g);
-    assert!(res.is_ok());
+    comm.acknowledge(&p, &g)?;
 
     // M intercepts and changes g -> p
     comm.dh.g = comm.dh.p.clone();
 
     // A participates generating a public key
-    let pub_key_a = comm.participate(pk_a, iv_a);
+    let pub_key_a = comm.participate(pk_a, iv_a)?;
 
     // B participates generating a public key
-    let pub_key_b = comm.participate(pk_b, iv_b);
+    let pub_key_b = comm.participate(pk_b, iv_b)?;
 
     // Computed pub_key_a & pub_key_b at this point must be 0. And so does
     // shared secret key, sk. This is because now g = p. Hence,
@@ -95,19 +92,18 @@ pub fn simulate_crack(
         .collect::<Vec<u8>>();
 
     // A encrypts message and sends ciphertext + A's iv to B
-    let cipher_a = comm.encrypt_msg(msg, pk_a, &pub_key_b, iv_a);
+    let cipher_a = comm.encrypt_msg(msg, pk_a, &pub_key_b, iv_a)?;
 
     // M is able intercept the A's encrypted message
-    let cracked_msg_a = aes128_cbc_decrypt(&cipher_a, &cracked_key, iv_a).unwrap();
-    assert_eq!(msg, cracked_msg_a.as_slice());
+    let cracked_msg_a = aes128_cbc_decrypt(&cipher_a, &cracked_key, iv_a)?;
 
     // B encrypts message and sends ciphertext + B's iv to A
-    let cipher_b = comm.encrypt_msg(msg, pk_b, &pub_key_a, iv_b);
+    let cipher_b = comm.encrypt_msg(msg, pk_b, &pub_key_a, iv_b)?;
 
     // M is able intercept the B's encrypted message
-    let cracked_msg_b = aes128_cbc_decrypt(&cipher_b, &cracked_key, iv_b).unwrap();
+    let cracked_msg_b = aes128_cbc_decrypt(&cipher_b, &cracked_key, iv_b)?;
 
-    (cracked_msg_a, cracked_msg_b)
+    Ok((cracked_msg_a, cracked_msg_b))
 }
 
 #[cfg(test)]
@@ -128,8 +124,21 @@ mod test {
     #[test]
     fn test_c35() {
         let msg = b"hello B!";
-        let (cracked_msg_a, cracked_msg_b) = simulate_crack(P, G, PK_A, PK_B, IV_A, IV_B, msg);
+        let (cracked_msg_a, cracked_msg_b) =
+            simulate_crack(P, G, PK_A, PK_B, IV_A, IV_B, msg).unwrap();
         assert_eq!(msg, cracked_msg_a.as_slice());
         assert_eq!(msg, cracked_msg_b.as_slice());
     }
+
+    #[test]
+    fn test_c35_protocol_state() {
+        let (p, g, mut comm) = Comms::init(P, G);
+        assert!(matches!(
+            comm.participate(PK_A, IV_A),
+            Err(CryptoError::ProtocolState(_))
+        ));
+        assert!(comm.acknowledge(&p, &[2]).is_err());
+        comm.acknowledge(&p, &g).unwrap();
+        assert!(comm.participate(PK_A, IV_A).is_ok());
+    }
 }
@@ -0,0 +1,114 @@
+use crate::utils::dsa::{Signature, DSA};
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+const P: &str = "800000000000000089e1855218a0e7dac38136ffafa72eda7859f2171e25e65eac698c1702578b07dc2a1076da241c76c62d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebeac04f48c3c84afb796d61e5a4f9a8fda812ab59494232c7d2b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc871a584471bb1";
+const Q: &str = "f4f47f05794b256174bba6e9b396a7707e563c5b";
+const G: &str = "5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119458fef538b8fa4046c8db53039db620c094c9fa077ef389b5322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a0470f5b64c36b625a097f1651fe775323556fe00b3608c887892878480e99041be601a62166ca6894bdd41a7054ec89f756ba9fc95302291";
+
+fn from_hex(hex: &str) -> BigUint {
+    BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+}
+
+pub fn challenge_dsa() -> DSA {
+    DSA::new(&from_hex(P), &from_hex(Q), &from_hex(G))
+}
+
+// Verifier which blindly uses the (possibly attacker supplied) generator g
+pub struct Verifier {
+    pub dsa: DSA,
+    check_range: bool,
+}
+
+impl Verifier {
+    pub fn new(g: &BigUint, check_range: bool) -> Self {
+        Self {
+            dsa: DSA::new(&from_hex(P), &from_hex(Q), g),
+            check_range,
+        }
+    }
+
+    pub fn verify(&self, msg: &[u8], sig: &Signature, y: &BigUint) -> bool {
+        if self.check_range {
+            self.dsa.verify(msg, sig, y)
+        } else {
+            self.dsa.verify_unchecked(msg, sig, y)
+        }
+    }
+}
+
+// Attacker
+pub fn forge_zero_g_signature(q: &BigUint) -> Signature {
+    // With g = 0, r = (g^k mod p) mod q = 0 and the verifier computes
+    // v = (g^u1 * y^u2 mod p) mod q = 0 (since y = g^x = 0 too).
+    // So r = 0 along with any s matches, for any message.
+    Signature {
+        r: BigUint::zero(),
+        s: rand::thread_rng().gen_biguint_range(&One::one(), q),
+    }
+}
+
+pub fn magic_signature(y: &BigUint, p: &BigUint, q: &BigUint) -> Signature {
+    // With g = p + 1, g^u1 = 1 mod p so the verifier computes
+    // v = (y^u2 mod p) mod q where u2 = r * s^-1.
+    // Choosing r = (y^z mod p) mod q and s = r * z^-1 mod q gives u2 = z,
+    // hence v = (y^z mod p) mod q = r for any message.
+    let z = rand::thread_rng().gen_biguint_range(&One::one(), q);
+    let r = y.modpow(&z, p) % q;
+    let s = (&r * z.modinv(q).unwrap()) % q;
+    Signature { r, s }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c45_zero_g() {
+        let (_, y) = challenge_dsa().gen_key_pair();
+        let zero = BigUint::zero();
+
+        // A signer tricked into g = 0 produces r = 0 signatures
+        let signer = DSA::new(&from_hex(P), &from_hex(Q), &zero);
+        let (x, _) = signer.gen_key_pair();
+        let k = rand::thread_rng().gen_biguint_range(&One::one(), &signer.q);
+        let sig = signer.sign_with_k(b"Hello, world", &x, &k).unwrap();
+        assert!(sig.r.is_zero());
+
+        let lax = Verifier::new(&zero, false);
+        let strict = Verifier::new(&zero, true);
+        let public = signer.g.modpow(&x, &signer.p);
+
+        assert!(lax.verify(b"Goodbye, world", &sig, &public));
+        assert!(!strict.verify(b"Goodbye, world", &sig, &public));
+
+        let forged = forge_zero_g_signature(&lax.dsa.q);
+        for msg in [&b"Hello, world"[..], b"Goodbye, world"] {
+            assert!(lax.verify(msg, &forged, &zero));
+            assert!(!strict.verify(msg, &forged, &zero));
+            assert!(!strict.verify(msg, &forged, &y));
+        }
+    }
+
+    #[test]
+    fn test_c45_p_plus_one_g() {
+        let dsa = challenge_dsa();
+        let (x, y) = dsa.gen_key_pair();
+
+        // Sanity check of untampered parameters
+        let sig = dsa.sign(b"Hello, world", &x);
+        assert!(dsa.verify(b"Hello, world", &sig, &y));
+        assert!(!dsa.verify(b"Goodbye, world", &sig, &y));
+
+        let tampered_g = &dsa.p + 1_u8;
+        let strict = Verifier::new(&tampered_g, true);
+        let lax = Verifier::new(&tampered_g, false);
+
+        let magic = magic_signature(&y, &dsa.p, &dsa.q);
+        for msg in [&b"Hello, world"[..], b"Goodbye, world"] {
+            assert!(strict.verify(msg, &magic, &y));
+            assert!(lax.verify(msg, &magic, &y));
+        }
+        assert!(!dsa.verify(b"Hello, world", &magic, &y));
+    }
+}
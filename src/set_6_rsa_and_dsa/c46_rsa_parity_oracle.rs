@@ -0,0 +1,101 @@
+use crate::utils::rsa::RSA;
+use base64;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::cell::Cell;
+
+const SECRET: &str =
+    "VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==";
+
+type ProgressCallback<'a> = &'a mut dyn FnMut(&[u8]);
+
+pub struct ParityOracle {
+    rsa: RSA,
+    queries: Cell<usize>,
+}
+
+impl ParityOracle {
+    pub fn new(bits: u64) -> Self {
+        Self {
+            rsa: RSA::new(bits),
+            queries: Cell::new(0),
+        }
+    }
+
+    // Returns (e, n)
+    pub fn public_key(&self) -> (BigUint, BigUint) {
+        (self.rsa.e.clone(), self.rsa.n.clone())
+    }
+
+    pub fn encrypt_secret(&self) -> BigUint {
+        let secret = base64::decode(SECRET).unwrap();
+        self.rsa.encrypt(&BigUint::from_bytes_be(&secret))
+    }
+
+    // Only reveals whether decrypted plaintext is even
+    pub fn is_even(&self, c: &BigUint) -> bool {
+        self.queries.set(self.queries.get() + 1);
+        (self.rsa.decrypt(c) % 2_u8).is_zero()
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+// Attacker
+pub fn recover_plaintext(
+    oracle: &ParityOracle,
+    c: &BigUint,
+    mut on_progress: Option<ProgressCallback>,
+) -> Vec<u8> {
+    let (e, n) = oracle.public_key();
+
+    // Multiplying ciphertext by 2^e doubles the plaintext (mod n)
+    let doubler = BigUint::from(2_u8).modpow(&e, &n);
+
+    // Plaintext m always lies in the interval
+    // (n * num / denom, n * (num + 1) / denom) where denom = 2^i after i
+    // queries. If 2m (mod n) is even, no wraparound happened - m is in
+    // lower half of the interval, otherwise in upper half.
+    let mut num = BigUint::zero();
+    let mut denom = BigUint::one();
+    let mut cipher = c.clone();
+
+    for _ in 0..n.bits() {
+        cipher = (&cipher * &doubler) % &n;
+        num <<= 1;
+        denom <<= 1;
+        if !oracle.is_even(&cipher) {
+            num += 1_u8;
+        }
+
+        if let Some(callback) = on_progress.as_mut() {
+            let upper = (&n * (&num + 1_u8)) / &denom;
+            callback(&upper.to_bytes_be());
+        }
+    }
+
+    ((&n * (num + 1_u8)) / denom).to_bytes_be()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c46() {
+        let oracle = ParityOracle::new(1024);
+        let c = oracle.encrypt_secret();
+
+        let mut n_updates = 0;
+        let mut hollywood = |partial: &[u8]| {
+            n_updates += 1;
+            println!("{}", String::from_utf8_lossy(partial));
+        };
+        let plaintext = recover_plaintext(&oracle, &c, Some(&mut hollywood));
+
+        assert_eq!(n_updates, 1024);
+        assert_eq!(plaintext, base64::decode(SECRET).unwrap());
+    }
+}
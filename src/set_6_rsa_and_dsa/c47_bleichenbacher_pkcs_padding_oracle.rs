@@ -0,0 +1,167 @@
+use crate::error::Result;
+use crate::utils::rsa::{pad_pkcs1_v15, unpad_pkcs1_v15, RSA};
+use num_bigint::BigUint;
+use num_traits::One;
+use std::cell::Cell;
+
+pub struct PaddingOracle {
+    rsa: RSA,
+    queries: Cell<usize>,
+}
+
+impl PaddingOracle {
+    pub fn new(bits: u64) -> Self {
+        Self {
+            rsa: RSA::new(bits),
+            queries: Cell::new(0),
+        }
+    }
+
+    // Returns (e, n)
+    pub fn public_key(&self) -> (BigUint, BigUint) {
+        (self.rsa.e.clone(), self.rsa.n.clone())
+    }
+
+    pub fn encrypt(&self, msg: &[u8]) -> Result<BigUint> {
+        let padded = pad_pkcs1_v15(msg, self.rsa.k())?;
+        Ok(self.rsa.encrypt(&BigUint::from_bytes_be(&padded)))
+    }
+
+    // Only reveals whether decrypted plaintext starts with 00 02
+    pub fn is_pkcs_conforming(&self, c: &BigUint) -> bool {
+        self.queries.set(self.queries.get() + 1);
+        let m = self.rsa.decrypt(c).to_bytes_be();
+        // Leading zero byte is dropped by to_bytes_be
+        m.len() == self.rsa.k() - 1 && m[0] == 2
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+fn ceil_div(a: &BigUint, b: &BigUint) -> BigUint {
+    (a + b - 1_u8) / b
+}
+
+// Merges (a, b) into list of disjoint intervals
+fn insert_interval(intervals: &mut Vec<(BigUint, BigUint)>, a: BigUint, b: BigUint) {
+    let (mut a, mut b) = (a, b);
+    let mut i = 0;
+    while i < intervals.len() {
+        let (x, y) = &intervals[i];
+        if *x <= b && a <= *y {
+            a = a.min(x.clone());
+            b = b.max(y.clone());
+            intervals.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    intervals.push((a, b));
+}
+
+// Attacker
+pub fn bleichenbacher_attack(oracle: &PaddingOracle, c: &BigUint) -> Vec<u8> {
+    let (e, n) = oracle.public_key();
+    let k = n.bits().div_ceil(8) as usize;
+
+    let b = BigUint::one() << (8 * (k - 2));
+    let b2 = &b * 2_u8;
+    let b3 = &b * 3_u8;
+
+    // c * s^e decrypts to m * s (mod n)
+    let is_conforming = |s: &BigUint| oracle.is_pkcs_conforming(&((c * s.modpow(&e, &n)) % &n));
+
+    // Step 1: c is already PKCS conforming, so blinding is skipped (s0 = 1)
+    let mut intervals = vec![(b2.clone(), &b3 - 1_u8)];
+
+    // Step 2a: smallest s >= n/3B with conforming c * s^e
+    let mut s = ceil_div(&n, &b3);
+    while !is_conforming(&s) {
+        s += 1_u8;
+    }
+
+    loop {
+        // Step 3: narrow the set of solutions
+        let mut narrowed = Vec::new();
+        for (a, bb) in intervals.iter() {
+            let lo = a * &s + 1_u8;
+            let r_min = if lo > b3 {
+                ceil_div(&(lo - &b3), &n)
+            } else {
+                BigUint::from(0_u8)
+            };
+            let r_max = (bb * &s - &b2) / &n;
+
+            let mut r = r_min;
+            while r <= r_max {
+                let rn = &r * &n;
+                let new_a = a.clone().max(ceil_div(&(&b2 + &rn), &s));
+                let new_b = bb.clone().min((&b3 - 1_u8 + &rn) / &s);
+                if new_a <= new_b {
+                    insert_interval(&mut narrowed, new_a, new_b);
+                }
+                r += 1_u8;
+            }
+        }
+        intervals = narrowed;
+
+        // Step 4: a single interval of length 1 is the plaintext
+        if intervals.len() == 1 && intervals[0].0 == intervals[0].1 {
+            let m = intervals[0].0.to_bytes_be();
+            // Restore the leading zero byte
+            let padded = std::iter::once(0_u8).chain(m).collect::<Vec<u8>>();
+            return unpad_pkcs1_v15(&padded).unwrap();
+        }
+
+        if intervals.len() > 1 {
+            // Step 2b: searching with more than one interval left
+            s += 1_u8;
+            while !is_conforming(&s) {
+                s += 1_u8;
+            }
+        } else {
+            // Step 2c: searching with one interval left
+            let (a, bb) = &intervals[0];
+            let mut r = ceil_div(&((bb * &s - &b2) * 2_u8), &n);
+            'search: loop {
+                let rn = &r * &n;
+                let s_min = ceil_div(&(&b2 + &rn), bb);
+                let s_max = ceil_div(&(&b3 + &rn), a);
+                let mut s_i = s_min;
+                while s_i < s_max {
+                    if is_conforming(&s_i) {
+                        s = s_i;
+                        break 'search;
+                    }
+                    s_i += 1_u8;
+                }
+                r += 1_u8;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::CryptoError;
+
+    #[test]
+    fn test_c47() {
+        let oracle = PaddingOracle::new(256);
+        let msg = b"kick it, CC";
+        let c = oracle.encrypt(msg).unwrap();
+        assert!(oracle.is_pkcs_conforming(&c));
+
+        let recovered = bleichenbacher_attack(&oracle, &c);
+        assert_eq!(recovered, msg);
+
+        // 32 byte modulus leaves room for 21 bytes of message only
+        assert_eq!(
+            oracle.encrypt(&[0; 22]),
+            Err(CryptoError::MessageTooLong(22))
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod c45_dsa_parameter_tampering;
+pub mod c46_rsa_parity_oracle;
+pub mod c47_bleichenbacher_pkcs_padding_oracle;
+pub mod c48_bleichenbacher_complete_case;
@@ -0,0 +1,201 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::aes::aes128_cbc_mac;
+use crate::utils::bitwise::xor_bytes;
+
+const UNKNOWN_KEY: &[u8] = b"YELLOW SUBMARINE";
+const ZERO_IV: &[u8] = &[0; 16];
+
+#[derive(Debug, PartialEq)]
+pub struct Transfer {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+}
+
+fn parse_tx(from: &str, tx: &str) -> Option<Transfer> {
+    let (to, amount) = tx.split_once(':')?;
+    if to.is_empty() || !to.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(Transfer {
+        from: from.to_owned(),
+        to: to.to_owned(),
+        amount: amount.parse().ok()?,
+    })
+}
+
+// Client (signs requests only on behalf of the logged in account)
+pub fn sign_transfer(account: &str, to: &str, amount: u64) -> Result<Vec<u8>> {
+    let msg = format!("from={}&to={}&amount={}", account, to, amount);
+    let iv: Vec<u8> = (0..16).map(|_| rand::random::<u8>()).collect();
+    let mac = aes128_cbc_mac(msg.as_bytes(), UNKNOWN_KEY, &iv)?;
+
+    // message || IV || MAC
+    Ok([msg.as_bytes(), &iv, &mac].concat())
+}
+
+pub fn sign_tx_list(account: &str, txs: &[(&str, u64)]) -> Result<Vec<u8>> {
+    let tx_list = txs
+        .iter()
+        .map(|(to, amount)| format!("{}:{}", to, amount))
+        .collect::<Vec<String>>()
+        .join(";");
+    let msg = format!("from={}&tx_list={}", account, tx_list);
+    let mac = aes128_cbc_mac(msg.as_bytes(), UNKNOWN_KEY, ZERO_IV)?;
+
+    // message || MAC
+    Ok([msg.as_bytes(), &mac].concat())
+}
+
+// Server (attacker controlled IV)
+pub fn process_transfer(request: &[u8]) -> Option<Transfer> {
+    if request.len() < 32 {
+        return None;
+    }
+    let (msg, rest) = request.split_at(request.len() - 32);
+    let (iv, mac) = rest.split_at(16);
+    if aes128_cbc_mac(msg, UNKNOWN_KEY, iv).ok()? != mac {
+        return None;
+    }
+
+    let msg = String::from_utf8_lossy(msg);
+    let mut from = None;
+    let mut to = None;
+    let mut amount = None;
+    for kv in msg.split('&') {
+        match kv.split_once('=') {
+            Some(("from", v)) => from = Some(v),
+            Some(("to", v)) => to = Some(v),
+            Some(("amount", v)) => amount = Some(v),
+            _ => {}
+        }
+    }
+    parse_tx(from?, &format!("{}:{}", to?, amount?))
+}
+
+// Server (fixed zero IV)
+pub fn process_tx_list(request: &[u8]) -> Option<Vec<Transfer>> {
+    if request.len() < 16 {
+        return None;
+    }
+    let (msg, mac) = request.split_at(request.len() - 16);
+    if aes128_cbc_mac(msg, UNKNOWN_KEY, ZERO_IV).ok()? != mac {
+        return None;
+    }
+
+    let msg = String::from_utf8_lossy(msg);
+    let from = msg.strip_prefix("from=")?.split('&').next()?;
+    let (_, tx_list) = msg.split_once("&tx_list=")?;
+
+    // Malformed transactions are skipped
+    Some(
+        tx_list
+            .split(';')
+            .filter_map(|tx| parse_tx(from, tx))
+            .collect(),
+    )
+}
+
+// Attacker
+pub fn forge_transfer(victim: &str, attacker: &str, amount: u64) -> Result<Vec<u8>> {
+    // Account ids of same length keep "from=<id>" within the first block
+    if victim.len() != attacker.len() || "from=".len() + victim.len() > 16 {
+        return Err(CryptoError::AttackFailed(
+            "account ids must have the same length and fit in the first block".to_owned(),
+        ));
+    }
+
+    // Legitimately signed request moving money into own account
+    let request = sign_transfer(attacker, attacker, amount)?;
+    let msg_len = request.len() - 32;
+    let msg = &request[..msg_len];
+    let iv = &request[msg_len..msg_len + 16];
+    let mac = &request[msg_len + 16..];
+
+    // First block is XORed with the IV before encryption, so any change
+    // in first block can be cancelled out by the same change in the IV
+    let mut forged_msg = msg.to_vec();
+    forged_msg[5..5 + victim.len()].copy_from_slice(victim.as_bytes());
+    let forged_iv = xor_bytes(iv, &xor_bytes(&msg[..16], &forged_msg[..16]));
+
+    Ok([&forged_msg, &forged_iv, mac].concat())
+}
+
+pub fn forge_tx_list(captured: &[u8], attacker: &str, amount: u64) -> Result<Vec<u8>> {
+    if captured.len() < 16 {
+        return Err(CryptoError::AttackFailed(
+            "captured request is shorter than a MAC".to_owned(),
+        ));
+    }
+    let (victim_msg, victim_mac) = captured.split_at(captured.len() - 16);
+
+    // Own message whose first block will get mangled, with the transfer
+    // of interest following it
+    let own_request = sign_tx_list(attacker, &[(attacker, 1), (attacker, amount)])?;
+    let (own_msg, own_mac) = own_request.split_at(own_request.len() - 16);
+
+    // Pad the victim's message the same way the MAC did
+    let pad = 16 - victim_msg.len() % 16;
+    let padded_victim_msg = victim_msg
+        .iter()
+        .cloned()
+        .chain(std::iter::repeat_n(pad as u8, pad))
+        .collect::<Vec<u8>>();
+
+    // Chaining the victim's MAC into own first block makes the CBC state
+    // after it identical to the one that produced own MAC
+    let glue_block = xor_bytes(&own_msg[..16], victim_mac);
+
+    Ok([&padded_victim_msg, &glue_block, &own_msg[16..], own_mac].concat())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c49_attacker_iv() {
+        let request = sign_transfer("666", "123", 10).unwrap();
+        let transfer = process_transfer(&request).unwrap();
+        assert_eq!(transfer.from, "666");
+
+        let forged = forge_transfer("123", "666", 1_000_000).unwrap();
+        let transfer = process_transfer(&forged).unwrap();
+        assert_eq!(
+            transfer,
+            Transfer {
+                from: "123".to_owned(),
+                to: "666".to_owned(),
+                amount: 1_000_000,
+            }
+        );
+
+        for (victim, attacker) in [("12", "666"), ("12345678901234", "66666666666666")] {
+            assert!(matches!(
+                forge_transfer(victim, attacker, 10),
+                Err(CryptoError::AttackFailed(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_c49_fixed_iv() {
+        let captured = sign_tx_list("123", &[("456", 10), ("789", 20)]).unwrap();
+        assert_eq!(process_tx_list(&captured).unwrap().len(), 2);
+
+        let forged = forge_tx_list(&captured, "666", 1_000_000).unwrap();
+        let transfers = process_tx_list(&forged).unwrap();
+        assert!(transfers.iter().all(|t| t.from == "123"));
+        assert!(transfers
+            .iter()
+            .any(|t| t.to == "666" && t.amount == 1_000_000));
+
+        assert!(matches!(
+            forge_tx_list(&captured[..15], "666", 10),
+            Err(CryptoError::AttackFailed(_))
+        ));
+    }
+}
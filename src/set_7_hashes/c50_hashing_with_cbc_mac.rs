@@ -0,0 +1,84 @@
+use crate::error::Result;
+use crate::utils::aes::{aes128_cbc_encrypt, aes128_cbc_mac, aes128_decrypt_block};
+use crate::utils::bitwise::xor_bytes;
+
+const KEY: &[u8] = b"YELLOW SUBMARINE";
+const IV: &[u8] = &[0; 16];
+
+// Whether JavaScript would end a `//` comment within bytes: at a line feed,
+// carriage return or UTF-8 encoded line/paragraph separator (U+2028/U+2029)
+fn ends_comment(bytes: &[u8]) -> bool {
+    bytes.iter().any(|&b| b == b'\n' || b == b'\r')
+        || bytes
+            .windows(3)
+            .any(|w| w == [0xe2, 0x80, 0xa8] || w == [0xe2, 0x80, 0xa9])
+}
+
+pub fn cbc_mac_hash(msg: &[u8]) -> Result<Vec<u8>> {
+    aes128_cbc_mac(msg, KEY, IV)
+}
+
+// Attacker
+pub fn forge_snippet(target_hash: &[u8], prefix: &str) -> Result<Vec<u8>> {
+    // Everything after the prefix is commented out. Extra spaces before the
+    // comment change the CBC state, giving a new glue block to try.
+    let mut n_spaces = 0;
+    loop {
+        let mut snippet = format!("{}{}//", prefix, " ".repeat(n_spaces)).into_bytes();
+        let n_fill = (16 - snippet.len() % 16) % 16;
+        snippet.extend(std::iter::repeat_n(b' ', n_fill));
+
+        // CBC state after the prefix blocks (no padding as they're aligned)
+        let encrypted = aes128_cbc_encrypt(&snippet, KEY, IV)?;
+        let state = &encrypted[encrypted.len() - 32..encrypted.len() - 16];
+
+        // Snippet ends with the glue block, so hashing appends a full
+        // padding block after it. Work backwards from the target hash:
+        // target = E(pad ^ E(glue ^ state))
+        let pad = [16_u8; 16];
+        let before_pad = xor_bytes(&aes128_decrypt_block(target_hash, KEY)?, &pad);
+        let glue = xor_bytes(&aes128_decrypt_block(&before_pad, KEY)?, state);
+
+        // A line terminator would end the comment
+        if !ends_comment(&glue) {
+            snippet.extend(glue);
+            return Ok(snippet);
+        }
+        n_spaces += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c50() {
+        let original = b"alert('MZA who was that?');\n";
+        let target_hash = cbc_mac_hash(original).unwrap();
+        assert_eq!(
+            hex::encode(&target_hash),
+            "296b8d7cb78a243dda4d0a61d33bbdd1"
+        );
+
+        let prefix = "alert('Ayo, the Wu is back!');";
+        let forged = forge_snippet(&target_hash, prefix).unwrap();
+        assert_eq!(cbc_mac_hash(&forged).unwrap(), target_hash);
+
+        // Code is the prefix alone, rest of the snippet being one line comment
+        assert!(forged.starts_with(prefix.as_bytes()));
+        let rest = &forged[prefix.len()..];
+        let comment_start = rest.windows(2).position(|w| w == b"//").unwrap();
+        assert!(rest[..comment_start].iter().all(|&b| b == b' '));
+        assert!(!ends_comment(&rest[comment_start..]));
+    }
+
+    #[test]
+    fn test_c50_line_terminators() {
+        assert!(ends_comment(b"// x\n"));
+        assert!(ends_comment(b"// x\r"));
+        assert!(ends_comment(&[b'/', b'/', 0xe2, 0x80, 0xa8, b'x']));
+        assert!(ends_comment(&[b'/', b'/', 0xe2, 0x80, 0xa9]));
+        assert!(!ends_comment(&[b'/', b'/', 0xe2, 0x80, 0xaa]));
+    }
+}
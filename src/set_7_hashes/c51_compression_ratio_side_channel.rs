@@ -0,0 +1,151 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::aes::{aes128_cbc_encrypt, aes128_ctr};
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::collections::HashSet;
+use std::io::Write;
+
+const SESSION_ID: &str = "TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=";
+const BASE64_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
+
+// Characters absent from the request (and not a separator), so they
+// never compress
+const JUNK_CHARS: &[u8] = b"!@#$%^&*()`~[]|;,<>?_'\"\\";
+const SEPARATOR: &[u8] = b"{}";
+const MAX_JUNK: usize = 512;
+
+#[derive(Debug, PartialEq)]
+pub enum EncryptionMode {
+    CTR,
+    CBC,
+}
+
+fn format_request(p: &[u8]) -> Vec<u8> {
+    let headers = format!(
+        "POST / HTTP/1.1\nHost: hapless.com\nCookie: sessionid={}\nContent-Length: {}\n",
+        SESSION_ID,
+        p.len()
+    );
+    headers.bytes().chain(p.iter().cloned()).collect()
+}
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn gen_rand_bytes(n: usize) -> Vec<u8> {
+    (0..n).map(|_| rand::random::<u8>()).collect()
+}
+
+// Only reveals length of the compressed & encrypted request
+pub fn compression_oracle(p: &[u8], mode: &EncryptionMode) -> Result<usize> {
+    let compressed = compress(&format_request(p));
+    let key = gen_rand_bytes(16);
+    let ciphertext = match mode {
+        EncryptionMode::CTR => aes128_ctr(&compressed, &key, rand::random())?,
+        EncryptionMode::CBC => aes128_cbc_encrypt(&compressed, &key, &gen_rand_bytes(16))?,
+    };
+    Ok(ciphertext.len())
+}
+
+// Attacker
+fn gen_junk(n: usize) -> Vec<u8> {
+    // No trigram repeats, so no part of it matches an earlier one
+    let mut junk: Vec<u8> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut i = 0;
+    while junk.len() < n {
+        let c = JUNK_CHARS[i % JUNK_CHARS.len()];
+        i += 1;
+        let len = junk.len();
+        if len >= 2 && !seen.insert([junk[len - 2], junk[len - 1], c]) {
+            continue;
+        }
+        junk.push(c);
+    }
+    junk
+}
+
+fn guess_next_byte(known: &[u8], mode: &EncryptionMode, junk: &[u8]) -> Result<Option<u8>> {
+    let candidates = BASE64_CHARS.iter().chain(b"\n").cloned();
+
+    for n_junk in 0..junk.len() {
+        // "Two tries": guess then separator vs separator then guess. Both
+        // have the same literals, so the same length, unless the guess is
+        // correct and extends the match against the cookie, saving a
+        // literal. Saving of a few bits may be lost in byte (CTR) or block
+        // (CBC) rounding of length, so junk is added in front until exactly
+        // one guess shows up shorter.
+        let base = junk[..n_junk]
+            .iter()
+            .chain(b"sessionid=".iter())
+            .chain(known.iter())
+            .cloned()
+            .collect::<Vec<u8>>();
+        let mut winners = Vec::new();
+        for c in candidates.clone() {
+            let adjacent = [&base, &[c][..], SEPARATOR].concat();
+            let separated = [&base, SEPARATOR, &[c][..]].concat();
+            if compression_oracle(&adjacent, mode)? < compression_oracle(&separated, mode)? {
+                winners.push(c);
+            }
+        }
+
+        if winners.len() == 1 {
+            return Ok(Some(winners[0]));
+        }
+    }
+
+    Ok(None)
+}
+
+// The session id is only recovered once its terminating line break is
+// found, a byte without a unique best guess fails the attack
+fn recover_with_junk(mode: &EncryptionMode, junk: &[u8]) -> Result<String> {
+    let mut known: Vec<u8> = Vec::new();
+    loop {
+        match guess_next_byte(&known, mode, junk)? {
+            // Line break ends the cookie header
+            Some(b'\n') => return Ok(String::from_utf8_lossy(&known).to_string()),
+            Some(b) => known.push(b),
+            None => {
+                return Err(CryptoError::AttackFailed(format!(
+                    "no unique guess for byte {} of the session id",
+                    known.len()
+                )))
+            }
+        }
+    }
+}
+
+pub fn recover_session_id(mode: &EncryptionMode) -> Result<String> {
+    recover_with_junk(mode, &gen_junk(MAX_JUNK))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c51_ctr() {
+        let session_id = recover_session_id(&EncryptionMode::CTR).unwrap();
+        assert_eq!(session_id, SESSION_ID);
+    }
+
+    #[test]
+    fn test_c51_cbc() {
+        let session_id = recover_session_id(&EncryptionMode::CBC).unwrap();
+        assert_eq!(session_id, SESSION_ID);
+    }
+
+    #[test]
+    fn test_c51_no_unique_guess() {
+        // Without junk no request is tried, so the first byte has no guess
+        assert!(matches!(
+            recover_with_junk(&EncryptionMode::CBC, &[]),
+            Err(CryptoError::AttackFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::error::Result;
+use crate::utils::merkle_damgard::{gen_rand_block, AesMD, MerkleDamgard};
+use std::collections::HashMap;
+
+type BlockPair = (Vec<u8>, Vec<u8>);
+
+// Birthday search for two distinct blocks leading to the same state.
+// Returns (block1, block2, colliding state)
+pub fn find_collision<H: MerkleDamgard>(
+    h: &H,
+    state: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    let mut seen: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+    loop {
+        let block = gen_rand_block();
+        let next_state = h.compress(state, &block)?;
+        match seen.get(&next_state) {
+            Some(other) if *other != block => return Ok((other.clone(), block, next_state)),
+            _ => {
+                seen.insert(next_state, block);
+            }
+        }
+    }
+}
+
+// Joux's multicollision: n successive single block collisions, so any choice
+// of one block from each pair yields one of 2^n messages with the same state.
+// Returns (colliding block pairs, final state)
+pub fn multicollision<H: MerkleDamgard>(
+    h: &H,
+    state: &[u8],
+    n: usize,
+) -> Result<(Vec<BlockPair>, Vec<u8>)> {
+    let mut pairs = Vec::new();
+    let mut state = state.to_vec();
+    for _ in 0..n {
+        let (b1, b2, next_state) = find_collision(h, &state)?;
+        pairs.push((b1, b2));
+        state = next_state;
+    }
+    Ok((pairs, state))
+}
+
+// Message picking first (bit = 0) or second (bit = 1) block of i-th pair
+// according to i-th bit of choice
+pub fn pick_message(pairs: &[BlockPair], choice: u64) -> Vec<u8> {
+    pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (b1, b2))| if choice >> i & 1 == 0 { b1 } else { b2 })
+        .cloned()
+        .collect()
+}
+
+pub fn expand_multicollision(pairs: &[BlockPair]) -> Vec<Vec<u8>> {
+    (0..1_u64 << pairs.len())
+        .map(|choice| pick_message(pairs, choice))
+        .collect()
+}
+
+// Attacker
+pub fn cascade_collision(cheap: &AesMD, expensive: &AesMD) -> Result<(Vec<u8>, Vec<u8>)> {
+    // Expected 2^(b2/2) messages are needed for a collision in the
+    // expensive hash. Generate that many with cheap hash collisions (each
+    // costing only about 2^(b1/2)), running the expensive hash over all of
+    // them level by level (sharing common prefixes). Add more if unlucky.
+    let mut pairs: Vec<BlockPair> = Vec::new();
+    let mut cheap_state = cheap.initial_state();
+    let mut expensive_states = vec![(expensive.initial_state(), 0_u64)];
+    loop {
+        let (b1, b2, next_state) = find_collision(cheap, &cheap_state)?;
+        let i = pairs.len();
+        let mut next_states = Vec::with_capacity(expensive_states.len() * 2);
+        for (state, choice) in expensive_states.iter() {
+            next_states.push((expensive.compress(state, &b1)?, *choice));
+            next_states.push((expensive.compress(state, &b2)?, choice | 1 << i));
+        }
+        expensive_states = next_states;
+        pairs.push((b1, b2));
+        cheap_state = next_state;
+
+        if pairs.len() < expensive.state_len * 8 / 2 {
+            continue;
+        }
+        let mut seen = HashMap::new();
+        for (state, choice) in expensive_states.iter() {
+            if let Some(other) = seen.insert(state, *choice) {
+                return Ok((pick_message(&pairs, other), pick_message(&pairs, *choice)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c52_multicollision() {
+        let h = AesMD::new(2).unwrap();
+        let (pairs, _) = multicollision(&h, &h.initial_state(), 4).unwrap();
+        let msgs = expand_multicollision(&pairs);
+        assert_eq!(msgs.len(), 16);
+
+        let digest = h.hash(&msgs[0]).unwrap();
+        for (i, msg) in msgs.iter().enumerate() {
+            assert_eq!(h.hash(msg).unwrap(), digest);
+            assert!(msgs[i + 1..].iter().all(|other| other != msg));
+        }
+    }
+
+    #[test]
+    fn test_c52() {
+        let f = AesMD::new(2).unwrap();
+        let g = AesMD::new(4).unwrap();
+        let (m1, m2) = cascade_collision(&f, &g).unwrap();
+        assert_ne!(m1, m2);
+
+        let h = |m: &[u8]| [f.hash(m).unwrap(), g.hash(m).unwrap()].concat();
+        assert_eq!(h(&m1), h(&m2));
+
+        // Combined 48 bit hash broken with roughly the 2^16 work of the
+        // 32 bit one (a few times that when unlucky) instead of 2^24
+        // expected of a 48 bit hash
+        assert!(f.calls() + g.calls() < 1 << 24);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::merkle_damgard::{gen_rand_block, MerkleDamgard, BLOCK_SIZE};
+use std::collections::HashMap;
+
+// Birthday search for blocks b1, b2 such that compressing b1 from state1
+// and b2 from state2 leads to the same state.
+// Returns (b1, b2, colliding state)
+pub fn find_collision_between<H: MerkleDamgard>(
+    h: &H,
+    state1: &[u8],
+    state2: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    let mut seen1: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+    let mut seen2: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+    loop {
+        let b1 = gen_rand_block();
+        let next1 = h.compress(state1, &b1)?;
+        if let Some(b2) = seen2.get(&next1) {
+            return Ok((b1, b2.clone(), next1));
+        }
+        seen1.insert(next1, b1);
+
+        let b2 = gen_rand_block();
+        let next2 = h.compress(state2, &b2)?;
+        if let Some(b1) = seen1.get(&next2) {
+            return Ok((b1.clone(), b2, next2));
+        }
+        seen2.insert(next2, b2);
+    }
+}
+
+// Set of messages of any length from k to k + 2^k - 1 blocks, all leading
+// to the same final state
+pub struct ExpandableMessage {
+    pub k: usize,
+    pub final_state: Vec<u8>,
+    // (1 block, 2^(k-1-i) + 1 blocks) colliding pair for i-th step
+    pairs: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl ExpandableMessage {
+    pub fn new<H: MerkleDamgard>(h: &H, k: usize) -> Result<Self> {
+        let mut state = h.initial_state();
+        let mut pairs = Vec::new();
+        for i in 0..k {
+            // Dummy blocks followed by the colliding last block
+            let dummy = vec![0_u8; BLOCK_SIZE << (k - 1 - i)];
+            let dummy_state = h.iterate(&state, &dummy)?;
+            let (short, last, next_state) = find_collision_between(h, &state, &dummy_state)?;
+
+            pairs.push((short, [dummy, last].concat()));
+            state = next_state;
+        }
+        Ok(Self {
+            k,
+            final_state: state,
+            pairs,
+        })
+    }
+
+    // Message of n_blocks blocks, if within k to k + 2^k - 1
+    pub fn produce(&self, n_blocks: usize) -> Option<Vec<u8>> {
+        if n_blocks < self.k || n_blocks > self.k + (1 << self.k) - 1 {
+            return None;
+        }
+        // Bits of extra no. of blocks decide the long ones to pick
+        let extra = n_blocks - self.k;
+        let msg = self
+            .pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (short, long))| {
+                if extra >> (self.k - 1 - i) & 1 == 1 {
+                    long
+                } else {
+                    short
+                }
+            })
+            .cloned()
+            .collect();
+        Some(msg)
+    }
+}
+
+// Attacker
+pub fn second_preimage<H: MerkleDamgard>(h: &H, msg: &[u8], k: usize) -> Result<Vec<u8>> {
+    // Intermediate states of the target message after each full block,
+    // keyed to no. of blocks processed to reach them. Only ones at least
+    // k + 1 blocks in can be reached by an expandable message & bridge
+    // block. A partial last block is just carried over to the forgery.
+    let n_blocks = msg.len() / BLOCK_SIZE;
+    if n_blocks <= k {
+        return Err(CryptoError::AttackFailed(format!(
+            "target of {} full blocks leaves no state past the expandable message",
+            n_blocks
+        )));
+    }
+    let mut states = HashMap::new();
+    let mut state = h.initial_state();
+    for (i, block) in msg.chunks_exact(BLOCK_SIZE).enumerate() {
+        state = h.compress(&state, block)?;
+        let n_processed = i + 1;
+        if (k + 1..=k + (1 << k)).contains(&n_processed) {
+            states.insert(state.clone(), n_processed);
+        }
+    }
+
+    let expandable = ExpandableMessage::new(h, k)?;
+
+    // Bridge block from final state of expandable message into target
+    let (bridge, j) = loop {
+        let block = gen_rand_block();
+        if let Some(&j) = states.get(&h.compress(&expandable.final_state, &block)?) {
+            break (block, j);
+        }
+    };
+
+    // Same length as target, so same padding and hash
+    let prefix = expandable.produce(j - 1).ok_or_else(|| {
+        CryptoError::AttackFailed(format!(
+            "no expandable message of {} blocks to bridge from",
+            j - 1
+        ))
+    })?;
+    Ok([prefix, bridge, msg[j * BLOCK_SIZE..].to_vec()].concat())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::merkle_damgard::AesMD;
+
+    #[test]
+    fn test_c53_expandable_message() {
+        let h = AesMD::new(2).unwrap();
+        let k = 4;
+        let expandable = ExpandableMessage::new(&h, k).unwrap();
+        for n_blocks in k..k + (1 << k) {
+            let msg = expandable.produce(n_blocks).unwrap();
+            assert_eq!(msg.len(), n_blocks * BLOCK_SIZE);
+            assert_eq!(
+                h.iterate(&h.initial_state(), &msg).unwrap(),
+                expandable.final_state
+            );
+        }
+        assert_eq!(expandable.produce(k - 1), None);
+        assert_eq!(expandable.produce(k + (1 << k)), None);
+    }
+
+    #[test]
+    fn test_c53() {
+        let h = AesMD::new(3).unwrap();
+        let k = 10;
+        let msg: Vec<u8> = (0..BLOCK_SIZE << k).map(|_| rand::random::<u8>()).collect();
+
+        let forged = second_preimage(&h, &msg, k).unwrap();
+        assert_eq!(forged.len(), msg.len());
+        assert_ne!(forged, msg);
+        assert_eq!(h.hash(&forged).unwrap(), h.hash(&msg).unwrap());
+    }
+
+    #[test]
+    fn test_c53_unaligned() {
+        let h = AesMD::new(2).unwrap();
+        let k = 4;
+        let msg: Vec<u8> = (0..BLOCK_SIZE * 20 + 7)
+            .map(|_| rand::random::<u8>())
+            .collect();
+
+        let forged = second_preimage(&h, &msg, k).unwrap();
+        assert_eq!(forged.len(), msg.len());
+        assert!(forged.ends_with(&msg[BLOCK_SIZE * 20..]));
+        assert_eq!(h.hash(&forged).unwrap(), h.hash(&msg).unwrap());
+
+        assert!(matches!(
+            second_preimage(&h, &msg[..BLOCK_SIZE * k + 7], k),
+            Err(CryptoError::AttackFailed(_))
+        ));
+    }
+}
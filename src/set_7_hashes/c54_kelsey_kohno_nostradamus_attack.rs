@@ -0,0 +1,131 @@
+use crate::error::{CryptoError, Result};
+use crate::set_7_hashes::c53_kelsey_schneier_expandable_messages::find_collision_between;
+use crate::utils::merkle_damgard::{gen_rand_block, md_pad, MerkleDamgard, BLOCK_SIZE};
+use std::collections::{HashMap, HashSet};
+
+// Binary tree of 2^k leaf states funnelling down into a single root state
+pub struct Diamond {
+    // States of each level, starting from leaves
+    states: Vec<Vec<Vec<u8>>>,
+    // Block taking each state of a level to its parent in the next one
+    blocks: Vec<Vec<Vec<u8>>>,
+}
+
+impl Diamond {
+    pub fn new<H: MerkleDamgard>(h: &H, k: usize) -> Result<Self> {
+        let mut leaves = HashSet::new();
+        while leaves.len() < 1 << k {
+            leaves.insert(h.compress(&h.initial_state(), &gen_rand_block())?);
+        }
+
+        let mut states: Vec<Vec<Vec<u8>>> = vec![leaves.into_iter().collect()];
+        let mut blocks = Vec::new();
+        for _ in 0..k {
+            let level = states.last().unwrap();
+            let mut next_level = Vec::new();
+            let mut level_blocks = Vec::new();
+            for pair in level.chunks(2) {
+                let (b1, b2, next_state) = find_collision_between(h, &pair[0], &pair[1])?;
+                level_blocks.push(b1);
+                level_blocks.push(b2);
+                next_level.push(next_state);
+            }
+            blocks.push(level_blocks);
+            states.push(next_level);
+        }
+
+        Ok(Self { states, blocks })
+    }
+
+    pub fn k(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn root(&self) -> &[u8] {
+        &self.states[self.k()][0]
+    }
+
+    pub fn leaves(&self) -> &[Vec<u8>] {
+        &self.states[0]
+    }
+
+    // Blocks leading from i-th leaf to the root
+    pub fn path(&self, leaf: usize) -> Vec<u8> {
+        (0..self.k())
+            .flat_map(|level| self.blocks[level][leaf >> level].clone())
+            .collect()
+    }
+}
+
+// Hash committed to up front, for messages with a prefix of fixed no.
+// of blocks
+pub struct Prediction {
+    pub hash: Vec<u8>,
+    prefix_blocks: usize,
+    diamond: Diamond,
+}
+
+impl Prediction {
+    pub fn commit<H: MerkleDamgard>(h: &H, k: usize, prefix_blocks: usize) -> Result<Self> {
+        let diamond = Diamond::new(h, k)?;
+
+        // prefix || glue block || path through diamond
+        let total_len = (prefix_blocks + 1 + k) * BLOCK_SIZE;
+        let hash = h.iterate(diamond.root(), &md_pad(&[], total_len))?;
+
+        Ok(Self {
+            hash,
+            prefix_blocks,
+            diamond,
+        })
+    }
+
+    pub fn forge<H: MerkleDamgard>(&self, h: &H, prefix: &[u8]) -> Result<Vec<u8>> {
+        if prefix.len() > self.prefix_blocks * BLOCK_SIZE {
+            return Err(CryptoError::MessageTooLong(prefix.len()));
+        }
+        let mut msg = prefix.to_vec();
+        msg.resize(self.prefix_blocks * BLOCK_SIZE, b' ');
+        let state = h.iterate(&h.initial_state(), &msg)?;
+
+        // Glue block from prefix state into one of the leaves
+        let leaves: HashMap<&Vec<u8>, usize> = self
+            .diamond
+            .leaves()
+            .iter()
+            .enumerate()
+            .map(|(i, leaf)| (leaf, i))
+            .collect();
+        let (glue, leaf) = loop {
+            let block = gen_rand_block();
+            if let Some(&i) = leaves.get(&h.compress(&state, &block)?) {
+                break (block, i);
+            }
+        };
+
+        msg.extend(glue);
+        msg.extend(self.diamond.path(leaf));
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::merkle_damgard::AesMD;
+
+    #[test]
+    fn test_c54() {
+        let h = AesMD::new(3).unwrap();
+        let prediction = Prediction::commit(&h, 8, 4).unwrap();
+
+        let scores = b"Yankees 5 Red Sox 3; Cubs 2 Cardinals 7; Giants 4 Dodgers 4;";
+        let forged = prediction.forge(&h, scores).unwrap();
+        assert!(forged.starts_with(scores));
+        assert_eq!(h.hash(&forged).unwrap(), prediction.hash);
+        assert_eq!(
+            prediction.forge(&h, &[b' '; 65]),
+            Err(CryptoError::MessageTooLong(65))
+        );
+    }
+}
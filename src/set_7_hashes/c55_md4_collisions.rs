@@ -0,0 +1,209 @@
+use crate::utils::md4::{
+    block_to_words, md4, r1, r1_inverse, step_values, words_to_block, INIT_STATE, R1_SHIFTS,
+    R2_SHIFTS,
+};
+
+// Sufficient conditions on a bit (numbered 1 to 32, as in Wang et al.) of a
+// step value: fixed, or equal/unequal to the same bit of another value
+#[derive(Clone, Copy)]
+enum Cond {
+    Zero,
+    One,
+    Eq(usize),
+    Ne(usize),
+}
+
+// Indices of a_i, d_i, c_i, b_i in md4::step_values
+const fn a(i: usize) -> usize {
+    4 * i
+}
+const fn d(i: usize) -> usize {
+    4 * i + 1
+}
+const fn c(i: usize) -> usize {
+    4 * i + 2
+}
+const fn b(i: usize) -> usize {
+    4 * i + 3
+}
+
+use Cond::*;
+
+#[rustfmt::skip]
+const CONDITIONS: &[(usize, u32, Cond)] = &[
+    // Round 1
+    (a(1), 7, Eq(b(0))),
+    (d(1), 7, Zero), (d(1), 8, Eq(a(1))), (d(1), 11, Eq(a(1))),
+    (c(1), 7, One), (c(1), 8, One), (c(1), 11, Zero), (c(1), 26, Eq(d(1))),
+    (b(1), 7, One), (b(1), 8, Zero), (b(1), 11, Zero), (b(1), 26, Zero),
+    (a(2), 8, One), (a(2), 11, One), (a(2), 26, Zero), (a(2), 14, Eq(b(1))),
+    (d(2), 14, Zero), (d(2), 19, Eq(a(2))), (d(2), 20, Eq(a(2))), (d(2), 21, Eq(a(2))),
+    (d(2), 22, Eq(a(2))), (d(2), 26, One),
+    (c(2), 13, Eq(d(2))), (c(2), 14, Zero), (c(2), 15, Eq(d(2))), (c(2), 19, Zero),
+    (c(2), 20, Zero), (c(2), 21, One), (c(2), 22, Zero),
+    (b(2), 13, One), (b(2), 14, One), (b(2), 15, Zero), (b(2), 17, Eq(c(2))),
+    (b(2), 19, Zero), (b(2), 20, Zero), (b(2), 21, Zero), (b(2), 22, Zero),
+    (a(3), 13, One), (a(3), 14, One), (a(3), 15, One), (a(3), 17, Zero), (a(3), 19, Zero),
+    (a(3), 20, Zero), (a(3), 21, Zero), (a(3), 23, Eq(b(2))), (a(3), 22, One),
+    (a(3), 26, Eq(b(2))),
+    (d(3), 13, One), (d(3), 14, One), (d(3), 15, One), (d(3), 17, Zero), (d(3), 20, Zero),
+    (d(3), 21, One), (d(3), 22, One), (d(3), 23, Zero), (d(3), 26, One), (d(3), 30, Eq(a(3))),
+    (c(3), 17, One), (c(3), 20, Zero), (c(3), 21, Zero), (c(3), 22, Zero), (c(3), 23, Zero),
+    (c(3), 26, Zero), (c(3), 30, One), (c(3), 32, Eq(d(3))),
+    (b(3), 20, Zero), (b(3), 21, One), (b(3), 22, One), (b(3), 23, Eq(c(3))), (b(3), 26, One),
+    (b(3), 30, Zero), (b(3), 32, Zero),
+    (a(4), 23, Zero), (a(4), 26, Zero), (a(4), 27, Eq(b(3))), (a(4), 29, Eq(b(3))),
+    (a(4), 30, One), (a(4), 32, Zero),
+    (d(4), 23, Zero), (d(4), 26, Zero), (d(4), 27, One), (d(4), 29, One), (d(4), 30, Zero),
+    (d(4), 32, One),
+    (c(4), 19, Eq(d(4))), (c(4), 23, One), (c(4), 26, One), (c(4), 27, Zero), (c(4), 29, Zero),
+    (c(4), 30, Zero),
+    (b(4), 19, Zero), (b(4), 26, Eq(c(4))), (b(4), 27, One), (b(4), 29, One), (b(4), 30, Zero),
+    // Round 2
+    (a(5), 19, Eq(c(4))), (a(5), 26, One), (a(5), 27, Zero), (a(5), 29, One), (a(5), 32, One),
+    (d(5), 19, Eq(a(5))), (d(5), 26, Eq(b(4))), (d(5), 27, Eq(b(4))), (d(5), 29, Eq(b(4))),
+    (d(5), 32, Eq(b(4))),
+    (c(5), 26, Eq(d(5))), (c(5), 27, Eq(d(5))), (c(5), 29, Eq(d(5))), (c(5), 30, Eq(d(5))),
+    (c(5), 32, Eq(d(5))),
+    (b(5), 29, Eq(c(5))), (b(5), 30, One), (b(5), 32, Zero),
+    (a(6), 29, One), (a(6), 32, One),
+    (d(6), 29, Eq(b(5))),
+    (c(6), 29, Eq(d(6))), (c(6), 30, Ne(d(6))), (c(6), 32, Ne(d(6))),
+    // Round 3
+    (b(9), 32, One),
+    (a(10), 32, One),
+];
+
+fn conditions_of(var: usize) -> impl Iterator<Item = &'static (usize, u32, Cond)> {
+    CONDITIONS.iter().filter(move |(v, _, _)| *v == var)
+}
+
+fn holds(v: &[u32], (var, bit, cond): &(usize, u32, Cond)) -> bool {
+    let x = v[*var] >> (bit - 1) & 1;
+    match cond {
+        Zero => x == 0,
+        One => x == 1,
+        Eq(other) => x == v[*other] >> (bit - 1) & 1,
+        Ne(other) => x != v[*other] >> (bit - 1) & 1,
+    }
+}
+
+// Value of var with the bits fixed to satisfy its conditions
+fn satisfy(v: &[u32], var: usize) -> u32 {
+    conditions_of(var).fold(v[var], |x, (_, bit, cond)| {
+        let mask = 1 << (bit - 1);
+        match cond {
+            Zero => x & !mask,
+            One => x | mask,
+            Eq(other) => x ^ ((x ^ v[*other]) & mask),
+            Ne(other) => x ^ ((x ^ !v[*other]) & mask),
+        }
+    })
+}
+
+// Round 1 step computing v[i + 4] from m[i]
+fn round1_step(v: &[u32], m: &[u32; 16], i: usize) -> u32 {
+    r1(v[i], v[i + 3], v[i + 2], v[i + 1], m[i], R1_SHIFTS[i % 4])
+}
+
+// Message word making round 1 step i output v[i + 4]
+fn round1_word(v: &[u32], i: usize) -> u32 {
+    r1_inverse(
+        v[i + 4],
+        v[i],
+        v[i + 3],
+        v[i + 2],
+        v[i + 1],
+        R1_SHIFTS[i % 4],
+    )
+}
+
+// Single-step modification: fix each round 1 value directly & derive the
+// message word producing it
+fn modify_round1(m: &mut [u32; 16]) -> Vec<u32> {
+    let [a0, b0, c0, d0] = INIT_STATE;
+    let mut v = vec![a0, d0, c0, b0];
+    for i in 0..16 {
+        v.push(round1_step(&v, m, i));
+        v[i + 4] = satisfy(&v, i + 4);
+        m[i] = round1_word(&v, i);
+    }
+    v
+}
+
+// Multi-step modification of a round 2 value computed from message word
+// m[k] (and written at round 1 step k to v[k + 4]), with rotation s in
+// round 2. Flipping a bit of the round 1 value flips bit `rot` higher in
+// the round 2 one; next 4 words are then adjusted so that rest of round 1
+// stays the same.
+fn modify_round2(m: &mut [u32; 16], v: &mut [u32], var: usize, k: usize, s: u32) {
+    let rot = s - R1_SHIFTS[k % 4];
+    for (_, bit, _) in conditions_of(var) {
+        let r2_values = step_values(&INIT_STATE, m);
+        if conditions_of(var)
+            .filter(|(_, b, _)| b == bit)
+            .all(|cond| holds(&r2_values, cond))
+        {
+            continue;
+        }
+
+        v[k + 4] ^= 1 << ((bit - 1 + 32 - rot) % 32);
+        for (i, word) in m.iter_mut().enumerate().skip(k).take(5) {
+            *word = round1_word(v, i);
+        }
+    }
+}
+
+fn apply_differential(m: &[u32; 16]) -> [u32; 16] {
+    let mut m_prime = *m;
+    m_prime[1] = m[1].wrapping_add(1 << 31);
+    m_prime[2] = m[2].wrapping_add(1 << 31).wrapping_sub(1 << 28);
+    m_prime[12] = m[12].wrapping_sub(1 << 16);
+    m_prime
+}
+
+// Attacker
+// Returns colliding messages & no. of tries it took
+pub fn find_collision(max_tries: usize) -> Option<(Vec<u8>, Vec<u8>, usize)> {
+    for tries in 1..=max_tries {
+        let block: Vec<u8> = (0..64).map(|_| rand::random::<u8>()).collect();
+        let mut m = block_to_words(&block);
+
+        let mut v = modify_round1(&mut m);
+        modify_round2(&mut m, &mut v, a(5), 0, R2_SHIFTS[0]);
+        modify_round2(&mut m, &mut v, d(5), 4, R2_SHIFTS[1]);
+
+        let m_prime = apply_differential(&m);
+        let msg = words_to_block(&m);
+        let msg_prime = words_to_block(&m_prime);
+        if md4(&msg) == md4(&msg_prime) {
+            return Some((msg, msg_prime, tries));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c55_round1() {
+        let block: Vec<u8> = (0..64).map(|_| rand::random::<u8>()).collect();
+        let mut m = block_to_words(&block);
+        modify_round1(&mut m);
+
+        let v = step_values(&INIT_STATE, &m);
+        assert!(CONDITIONS
+            .iter()
+            .filter(|(var, _, _)| *var < a(5))
+            .all(|cond| holds(&v, cond)));
+    }
+
+    #[test]
+    fn test_c55() {
+        let (m1, m2, _) = find_collision(1 << 22).unwrap();
+        assert_ne!(m1, m2);
+        assert_eq!(md4(&m1), md4(&m2));
+    }
+}
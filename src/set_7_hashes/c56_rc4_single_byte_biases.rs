@@ -0,0 +1,117 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::rc4::RC4;
+use base64;
+use std::cell::Cell;
+
+const COOKIE: &str = "QkUgU1VSRSBUTyBEUklOSyBZT1VSIE9WQUxUSU5F";
+
+// Most likely keystream bytes at (1 indexed) positions 16 & 32, and their
+// relative excess probability over uniform 1/256 (measured)
+const Z16_BIAS: (usize, u8, f64) = (15, 240, 0.042);
+const Z32_BIAS: (usize, u8, f64) = (31, 224, 0.029);
+
+pub struct CookieOracle {
+    cookie: Vec<u8>,
+    queries: Cell<usize>,
+}
+
+impl CookieOracle {
+    pub fn new() -> Self {
+        Self::with_cookie(&base64::decode(COOKIE).unwrap())
+    }
+
+    pub fn with_cookie(cookie: &[u8]) -> Self {
+        Self {
+            cookie: cookie.to_vec(),
+            queries: Cell::new(0),
+        }
+    }
+
+    // Encrypts request followed by the cookie under a fresh random key
+    pub fn encrypt(&self, request: &[u8]) -> Result<Vec<u8>> {
+        self.queries.set(self.queries.get() + 1);
+        let key: [u8; 16] = rand::random();
+        let plaintext = [request, &self.cookie].concat();
+        Ok(RC4::new(&key)?.apply(&plaintext))
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl Default for CookieOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Attacker
+// Recovers the cookie with n_samples encryptions for each prefix length.
+// Cookie bytes beyond keystream position 32 are out of reach of the biases.
+pub fn recover_cookie(oracle: &CookieOracle, n_samples: usize) -> Result<Vec<u8>> {
+    let cookie_len = oracle.encrypt(b"")?.len();
+    if cookie_len > Z32_BIAS.0 + 1 {
+        return Err(CryptoError::AttackFailed(format!(
+            "cookie of {} bytes is beyond the biases",
+            cookie_len
+        )));
+    }
+
+    // Padding the request with a prefix moves each cookie byte over the
+    // biased positions in turn. Every ciphertext byte at a biased position
+    // is then a vote for (ciphertext byte ^ biased keystream byte) as the
+    // plaintext byte there, weighted by log likelihood ratio of the bias.
+    let mut votes = vec![[0_f64; 256]; cookie_len];
+    for prefix_len in 0..=Z32_BIAS.0 {
+        let targets: Vec<(usize, u8, f64)> = [Z16_BIAS, Z32_BIAS]
+            .iter()
+            .filter(|(pos, _, _)| *pos >= prefix_len && pos - prefix_len < cookie_len)
+            .map(|&(pos, z, bias)| (pos, z, bias.ln_1p()))
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+
+        let prefix = vec![b'A'; prefix_len];
+        for _ in 0..n_samples {
+            let ciphertext = oracle.encrypt(&prefix)?;
+            for &(pos, z, weight) in targets.iter() {
+                votes[pos - prefix_len][(ciphertext[pos] ^ z) as usize] += weight;
+            }
+        }
+    }
+
+    Ok(votes
+        .iter()
+        .map(|scores| {
+            (0..=255)
+                .max_by(|&b1, &b2| scores[b1 as usize].total_cmp(&scores[b2 as usize]))
+                .unwrap()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c56() {
+        // Full cookie needs about 2^24 samples for each of 32 prefix
+        // lengths; a two byte one needs only 4 prefix lengths, and is still
+        // recovered reliably with 2^22 samples
+        let cookie = b"BE";
+        let oracle = CookieOracle::with_cookie(cookie);
+        assert_eq!(recover_cookie(&oracle, 1 << 22).unwrap(), cookie);
+    }
+
+    #[test]
+    fn test_c56_cookie_too_long() {
+        let oracle = CookieOracle::with_cookie(&[b'A'; 40]);
+        assert!(matches!(
+            recover_cookie(&oracle, 1 << 22),
+            Err(CryptoError::AttackFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod c49_cbc_mac_message_forgery;
+pub mod c50_hashing_with_cbc_mac;
+pub mod c51_compression_ratio_side_channel;
+pub mod c52_iterated_hash_multicollisions;
+pub mod c53_kelsey_schneier_expandable_messages;
+pub mod c54_kelsey_kohno_nostradamus_attack;
+pub mod c55_md4_collisions;
+pub mod c56_rc4_single_byte_biases;
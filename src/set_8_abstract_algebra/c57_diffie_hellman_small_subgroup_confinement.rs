@@ -0,0 +1,159 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::diffie_hellman::DH;
+use crate::utils::sha::hmac_sha1;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use std::cell::Cell;
+
+const P: &str = "7199773997391911030609999317773941274322764333428698921736339643928346453700085358802973900485592910475480089726140708102474957429903531369589969318716771";
+const G: &str = "4565356397095740655436854503483826832136106141639563487732438195343690437606117828318042418238184896212352329118608100083187535033402010599512641674644143";
+const Q: &str = "236234353446506858198510045061214171961";
+
+const MESSAGE: &[u8] = b"crazy flamboyant for the rap enjoyment";
+
+// Factors of j are searched for up to this bound
+pub const FACTOR_BOUND: u64 = 1 << 16;
+
+pub fn from_dec(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 10).unwrap()
+}
+
+// Returns (p, g, q)
+pub fn challenge_params() -> (BigUint, BigUint, BigUint) {
+    (from_dec(P), from_dec(G), from_dec(Q))
+}
+
+pub struct Bob {
+    pub dh: DH,
+    x: Vec<u8>,
+    queries: Cell<usize>,
+}
+
+impl Bob {
+    // Bob with a random long-term secret x in [1, q)
+    pub fn new(p: &BigUint, g: &BigUint, q: &BigUint) -> Self {
+        let x = rand::thread_rng().gen_biguint_range(&BigUint::one(), q);
+        Self {
+            dh: DH::new(&p.to_bytes_be(), &g.to_bytes_be()),
+            x: x.to_bytes_be(),
+            queries: Cell::new(0),
+        }
+    }
+
+    pub fn pub_key(&self) -> Vec<u8> {
+        self.dh.gen_pub_key(&self.x)
+    }
+
+    // Responds to Alice's public key h with (message, MAC under shared key)
+    pub fn respond(&self, h: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        self.queries.set(self.queries.get() + 1);
+        let k = self.dh.gen_session_key(h, &self.x);
+        (MESSAGE.to_vec(), hmac_sha1(&k, MESSAGE))
+    }
+
+    pub fn is_secret(&self, x: &BigUint) -> bool {
+        BigUint::from_bytes_be(&self.x) == *x
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+// Distinct prime factors of n below bound, by trial division
+pub fn small_factors(n: &BigUint, bound: u64) -> Vec<u64> {
+    let mut n = n.clone();
+    let mut factors = Vec::new();
+    for f in 2..bound {
+        if (&n % f).is_zero() {
+            factors.push(f);
+            while (&n % f).is_zero() {
+                n /= f;
+            }
+        }
+    }
+    factors
+}
+
+// Chinese remainder theorem over (residue, modulus) pairs with pairwise
+// coprime moduli. Returns (x, product of moduli)
+pub fn crt(residues: &[(BigUint, BigUint)]) -> (BigUint, BigUint) {
+    let m: BigUint = residues.iter().map(|(_, m_i)| m_i).product();
+    let x = residues.iter().fold(BigUint::zero(), |x, (r_i, m_i)| {
+        let n_i = &m / m_i;
+        let inv = (&n_i % m_i).modinv(m_i).unwrap();
+        (x + r_i * n_i * inv) % &m
+    });
+    (x, m)
+}
+
+// Random element of order r (a prime dividing p - 1)
+pub fn element_of_order(p: &BigUint, r: u64) -> BigUint {
+    let exp = (p - 1_u8) / r;
+    loop {
+        let h = rand::thread_rng()
+            .gen_biguint_range(&BigUint::from(2_u8), p)
+            .modpow(&exp, p);
+        if !h.is_one() {
+            return h;
+        }
+    }
+}
+
+// Attacker
+// Recovers Bob's secret x mod r by sending him an element of small order r
+// (forcing shared key into a subgroup of just r elements) and brute forcing
+// the key against his MAC
+pub fn secret_mod(bob: &Bob, p: &BigUint, r: u64) -> Result<BigUint> {
+    let h = element_of_order(p, r);
+    let (msg, mac) = bob.respond(&h.to_bytes_be());
+
+    let mut k = BigUint::one();
+    for x in 0..r {
+        if hmac_sha1(&k.to_bytes_be(), &msg) == mac {
+            return Ok(BigUint::from(x));
+        }
+        k = (k * &h) % p;
+    }
+    Err(CryptoError::AttackFailed(
+        "no key matched the MAC".to_owned(),
+    ))
+}
+
+pub fn recover_secret(bob: &Bob, p: &BigUint, q: &BigUint) -> Result<BigUint> {
+    let j = (p - 1_u8) / q;
+
+    // Secret is below q, so residues modulo small factors of j whose
+    // product exceeds q determine it
+    let mut residues = Vec::new();
+    let mut product = BigUint::one();
+    for r in small_factors(&j, FACTOR_BOUND) {
+        if product > *q {
+            break;
+        }
+        residues.push((secret_mod(bob, p, r)?, BigUint::from(r)));
+        product *= r;
+    }
+    if product <= *q {
+        return Err(CryptoError::AttackFailed(
+            "not enough small factors of j".to_owned(),
+        ));
+    }
+
+    Ok(crt(&residues).0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c57() {
+        let (p, g, q) = challenge_params();
+        let bob = Bob::new(&p, &g, &q);
+
+        let x = recover_secret(&bob, &p, &q).unwrap();
+        assert!(bob.is_secret(&x));
+        assert_eq!(g.modpow(&x, &p), BigUint::from_bytes_be(&bob.pub_key()));
+    }
+}
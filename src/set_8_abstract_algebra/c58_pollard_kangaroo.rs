@@ -0,0 +1,192 @@
+use crate::error::{CryptoError, Result};
+use crate::set_8_abstract_algebra::c57_diffie_hellman_small_subgroup_confinement::{
+    crt, from_dec, secret_mod, small_factors, Bob, FACTOR_BOUND,
+};
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive, Zero};
+
+const P: &str = "11470374874925275658116663507232161402086650258453896274534991676898999262641581519101074740642369848233294239851519212341844337347119899874391456329785623";
+const Q: &str = "335062023296420808191071248367701059461";
+const G: &str = "622952335333961296978159266084741085889881358738459939978290179936063635566740258555167783009058567397963466103140082647486611657350811560630587013183357";
+
+// Jump functions tried before giving up on the kangaroo
+pub const KANGAROO_TRIES: u32 = 8;
+
+// Returns (p, g, q)
+pub fn challenge_params() -> (BigUint, BigUint, BigUint) {
+    (from_dec(P), from_dec(G), from_dec(Q))
+}
+
+// Cyclic group the kangaroos jump around in, written multiplicatively
+pub trait Group {
+    type Element: Clone + PartialEq;
+
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    fn pow(&self, a: &Self::Element, e: &BigUint) -> Self::Element;
+
+    // Pseudorandom (but deterministic) index below k derived from a
+    fn index(&self, a: &Self::Element, k: usize) -> usize;
+}
+
+// Multiplicative group of integers mod p
+pub struct ModP<'a>(pub &'a BigUint);
+
+impl Group for ModP<'_> {
+    type Element = BigUint;
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % self.0
+    }
+
+    fn pow(&self, a: &BigUint, e: &BigUint) -> BigUint {
+        a.modpow(e, self.0)
+    }
+
+    fn index(&self, a: &BigUint, k: usize) -> usize {
+        (a % k).to_usize().unwrap()
+    }
+}
+
+// Pseudorandom jump function f(y) = 2^index(y), with all 2^i & g^(2^i)
+// precomputed
+struct Jumps<E> {
+    sizes: Vec<BigUint>,
+    factors: Vec<E>,
+}
+
+impl<E> Jumps<E> {
+    fn new<G: Group<Element = E>>(group: &G, g: &E, k: u32) -> Self {
+        let sizes: Vec<BigUint> = (0..k).map(|i| BigUint::one() << i).collect();
+        let factors = sizes.iter().map(|size| group.pow(g, size)).collect();
+        Self { sizes, factors }
+    }
+
+    // Jump from y: (distance, factor)
+    fn from<G: Group<Element = E>>(&self, group: &G, y: &E) -> (&BigUint, &E) {
+        let j = group.index(y, self.sizes.len());
+        (&self.sizes[j], &self.factors[j])
+    }
+}
+
+// Jump function parameter k & no. of tame kangaroo jumps n for an interval
+// of size w: mean jump about sqrt(w) / 2, with n four times that
+pub fn jump_params(w: &BigUint) -> (u32, BigUint) {
+    let half_sqrt = w.sqrt() >> 1;
+    let mut k = 1;
+    while ((BigUint::one() << k) - 1_u8) / k < half_sqrt {
+        k += 1;
+    }
+    let mean = ((BigUint::one() << k) - 1_u8) / k;
+    (k, mean * 4_u8)
+}
+
+// Pollard's kangaroo: discrete log of y to base g in the group, known to
+// be in the interval [a, b], with jump function parameter k & n tame jumps
+pub fn kangaroo<G: Group>(
+    group: &G,
+    g: &G::Element,
+    y: &G::Element,
+    (a, b): (&BigUint, &BigUint),
+    k: u32,
+    n: &BigUint,
+) -> Option<BigUint> {
+    let jumps = Jumps::new(group, g, k);
+
+    // Tame kangaroo from g^b leaves a trap at the end of its n jumps
+    let mut x_tame = BigUint::zero();
+    let mut y_tame = group.pow(g, b);
+    let mut i = BigUint::zero();
+    while i < *n {
+        let (size, factor) = jumps.from(group, &y_tame);
+        x_tame += size;
+        y_tame = group.mul(&y_tame, factor);
+        i += 1_u8;
+    }
+
+    // Wild kangaroo from y lands on the same path (and so in the trap) if
+    // y lies in the interval, else it passes the trap. Its last jump may
+    // take it past the trap before landing on the path, which gives a log
+    // below a.
+    let mut x_wild = BigUint::zero();
+    let mut y_wild = y.clone();
+    let max_distance = b - a + &x_tame;
+    while x_wild < max_distance {
+        let (size, factor) = jumps.from(group, &y_wild);
+        x_wild += size;
+        y_wild = group.mul(&y_wild, factor);
+        if y_wild == y_tame {
+            return (x_wild <= max_distance).then(|| b + x_tame - x_wild);
+        }
+    }
+    None
+}
+
+// Attacker
+// Small subgroup attack gives x mod r for product r of small factors of j
+// only, with x = n + m * r. Then y * g^-n = (g^r)^m, with m in
+// [0, (q - 1) / r] found with the kangaroo.
+pub fn recover_secret(bob: &Bob, p: &BigUint, g: &BigUint, q: &BigUint) -> Result<BigUint> {
+    let j = (p - 1_u8) / q;
+    let residues = small_factors(&j, FACTOR_BOUND)
+        .into_iter()
+        .map(|r| Ok((secret_mod(bob, p, r)?, BigUint::from(r))))
+        .collect::<Result<Vec<(BigUint, BigUint)>>>()?;
+    let (n, r) = crt(&residues);
+
+    let y = BigUint::from_bytes_be(&bob.pub_key());
+    let g_inv_n = g.modpow(&(q - &n % q), p);
+    let y_prime = (y * g_inv_n) % p;
+    let g_prime = g.modpow(&r, p);
+
+    let (a, b) = (BigUint::zero(), (q - 1_u8) / &r);
+    // Kangaroo misses with small probability; retry with another jump
+    // function if it does
+    let (k, n_jumps) = jump_params(&(&b - &a));
+    let m = (k..k + KANGAROO_TRIES)
+        .find_map(|k| kangaroo(&ModP(p), &g_prime, &y_prime, (&a, &b), k, &n_jumps))
+        .ok_or_else(|| CryptoError::AttackFailed("kangaroo missed".to_owned()))?;
+    Ok(n + m * r)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_interval(y: &str, b_bits: u32) {
+        let (p, g, _) = challenge_params();
+        let y = from_dec(y);
+        let (a, b) = (BigUint::zero(), BigUint::one() << b_bits);
+        let (k, n) = jump_params(&b);
+        let x = kangaroo(&ModP(&p), &g, &y, (&a, &b), k, &n).unwrap();
+        assert!(x <= b);
+        assert_eq!(g.modpow(&x, &p), y);
+    }
+
+    #[test]
+    fn test_c58_kangaroo() {
+        check_interval("7760073848032689505395005705677365876654629189298052775754597607446617558600394076764814236081991643094239886772481052254010323780165093955236429914607119", 20);
+        check_interval("9388897478013399550694114614498790691034187453089355259602614074132918843899833277397448144245883225611726912025846772975325932794909655215329941809013733", 40);
+    }
+
+    #[test]
+    fn test_c58_kangaroo_below_interval() {
+        // Wild kangaroo only reaches the trap by jumping past it, which
+        // must not be taken for a log below a
+        let (p, g, _) = challenge_params();
+        let (a, b) = (BigUint::one() << 20, BigUint::one() << 21);
+        let (k, n) = jump_params(&(&b - &a));
+        for x in [&a - 1_u8, &a - 1000_u32] {
+            let y = g.modpow(&x, &p);
+            assert_eq!(kangaroo(&ModP(&p), &g, &y, (&a, &b), k, &n), None);
+        }
+    }
+
+    #[test]
+    fn test_c58() {
+        let (p, g, q) = challenge_params();
+        let bob = Bob::new(&p, &g, &q);
+        let x = recover_secret(&bob, &p, &g, &q).unwrap();
+        assert!(bob.is_secret(&x));
+    }
+}
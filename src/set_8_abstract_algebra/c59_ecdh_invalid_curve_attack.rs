@@ -0,0 +1,154 @@
+use crate::error::{CryptoError, Result};
+use crate::set_8_abstract_algebra::c57_diffie_hellman_small_subgroup_confinement::{
+    crt, from_dec, small_factors, FACTOR_BOUND,
+};
+use crate::utils::ec::{Point, WeierstrassCurve, ECDH};
+use crate::utils::sha::hmac_sha1;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::cell::Cell;
+
+const MESSAGE: &[u8] = b"crazy flamboyant for the rap enjoyment";
+
+// Curves y^2 = x^3 - 95051x + b sharing a with the #59 curve, with (b,
+// order of the group of points)
+const INVALID_CURVES: [(u32, &str); 3] = [
+    (210, "233970423115425145550826547352470124412"),
+    (504, "233970423115425145544350131142039591210"),
+    (727, "233970423115425145545378039958152057148"),
+];
+
+// Shared key material from shared point: both its coordinates
+fn point_key(point: &Point) -> Vec<u8> {
+    match point {
+        Point::Infinity => Vec::new(),
+        Point::Affine(x, y) => [x.to_bytes_be(), y.to_bytes_be()].concat(),
+    }
+}
+
+pub struct Bob {
+    pub ecdh: ECDH,
+    x: BigUint,
+    validate: bool,
+    queries: Cell<usize>,
+}
+
+impl Bob {
+    pub fn new(validate: bool) -> Self {
+        let ecdh = ECDH::challenge_59();
+        let (x, _) = ecdh.gen_key_pair();
+        Self {
+            ecdh,
+            x,
+            validate,
+            queries: Cell::new(0),
+        }
+    }
+
+    // Responds to Alice's public key with (message, MAC under shared key),
+    // or nothing if validating & the key is invalid
+    pub fn respond(&self, pub_key: &Point) -> Option<(Vec<u8>, Vec<u8>)> {
+        self.queries.set(self.queries.get() + 1);
+        if self.validate && !self.ecdh.is_valid_pub_key(pub_key) {
+            return None;
+        }
+        let k = self.ecdh.gen_session_key(pub_key, &self.x);
+        Some((MESSAGE.to_vec(), hmac_sha1(&point_key(&k), MESSAGE)))
+    }
+
+    pub fn is_secret(&self, x: &BigUint) -> bool {
+        self.x == *x
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+// Attacker
+// Random point of order r (a prime dividing order of the curve's group)
+fn point_of_order(curve: &WeierstrassCurve, curve_order: &BigUint, r: u64) -> Point {
+    // Remove all factors r from the order, so that what's left of a random
+    // point lies in the r-part of the group, and multiply by r until the
+    // next multiple is infinity
+    let mut cofactor = curve_order.clone();
+    while (&cofactor % r).is_zero() {
+        cofactor /= r;
+    }
+    loop {
+        let mut point = curve.scalar_mul(&curve.random_point(), &cofactor);
+        if point == Point::Infinity {
+            continue;
+        }
+        loop {
+            let next = curve.scalar_mul(&point, &BigUint::from(r));
+            if next == Point::Infinity {
+                return point;
+            }
+            point = next;
+        }
+    }
+}
+
+// Bob's secret mod r from his response to a point of order r of an invalid
+// curve. Point addition doesn't involve b, so he computes on that curve.
+fn secret_mod(bob: &Bob, curve: &WeierstrassCurve, point: &Point, r: u64) -> Result<BigUint> {
+    let (msg, mac) = bob
+        .respond(point)
+        .ok_or_else(|| CryptoError::OracleRefused("invalid public key".to_owned()))?;
+    let mut k = Point::Infinity;
+    for x in 0..r {
+        if hmac_sha1(&point_key(&k), &msg) == mac {
+            return Ok(BigUint::from(x));
+        }
+        k = curve.add(&k, point);
+    }
+    Err(CryptoError::AttackFailed(
+        "no key matched the MAC".to_owned(),
+    ))
+}
+
+pub fn recover_secret(bob: &Bob) -> Result<BigUint> {
+    let (a, p, q) = (&bob.ecdh.curve.a, &bob.ecdh.curve.p, &bob.ecdh.q);
+
+    let mut residues = Vec::new();
+    let mut product = BigUint::one();
+    for (b, order) in INVALID_CURVES.iter() {
+        let curve = WeierstrassCurve::new(a, &BigUint::from(*b), p);
+        let order = from_dec(order);
+        for r in small_factors(&order, FACTOR_BOUND) {
+            let r_big = BigUint::from(r);
+            if product > *q || residues.iter().any(|(_, m)| *m == r_big) {
+                continue;
+            }
+            let point = point_of_order(&curve, &order, r);
+            residues.push((secret_mod(bob, &curve, &point, r)?, r_big));
+            product *= r;
+        }
+    }
+    if product <= *q {
+        return Err(CryptoError::AttackFailed(
+            "not enough small subgroups".to_owned(),
+        ));
+    }
+
+    Ok(crt(&residues).0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c59() {
+        let bob = Bob::new(false);
+        let x = recover_secret(&bob).unwrap();
+        assert!(bob.is_secret(&x));
+
+        let bob = Bob::new(true);
+        assert!(matches!(
+            recover_secret(&bob),
+            Err(CryptoError::OracleRefused(_))
+        ));
+    }
+}
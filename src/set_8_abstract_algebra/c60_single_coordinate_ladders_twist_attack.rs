@@ -0,0 +1,275 @@
+use crate::error::{CryptoError, Result};
+use crate::set_8_abstract_algebra::c57_diffie_hellman_small_subgroup_confinement::{
+    crt, small_factors,
+};
+use crate::set_8_abstract_algebra::c58_pollard_kangaroo::{
+    jump_params, kangaroo, Group, KANGAROO_TRIES,
+};
+use crate::utils::ec::{
+    inv_mod, sqrt_mod, sub_mod, MontgomeryCurve, MontgomeryECDH, Point, WeierstrassCurve, ECDH,
+};
+use crate::utils::sha::hmac_sha1;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, ToPrimitive, Zero};
+use std::cell::Cell;
+
+const MESSAGE: &[u8] = b"crazy flamboyant for the rap enjoyment";
+
+// Montgomery u = Weierstrass x - 178 for the #59/#60 curve
+const U_OFFSET: u32 = 178;
+
+pub struct Bob {
+    pub ecdh: MontgomeryECDH,
+    x: BigUint,
+    pub_key: BigUint,
+    queries: Cell<usize>,
+}
+
+impl Bob {
+    pub fn new() -> Self {
+        let ecdh = MontgomeryECDH::challenge_60();
+        let (x, pub_key) = ecdh.gen_key_pair();
+        Self {
+            ecdh,
+            x,
+            pub_key,
+            queries: Cell::new(0),
+        }
+    }
+
+    // Bob with the given secret, e.g. one known to be small
+    pub fn with_secret(x: &BigUint) -> Self {
+        let ecdh = MontgomeryECDH::challenge_60();
+        let pub_key = ecdh.curve.ladder(&ecdh.u, x);
+        Self {
+            ecdh,
+            x: x.clone(),
+            pub_key,
+            queries: Cell::new(0),
+        }
+    }
+
+    pub fn pub_key(&self) -> BigUint {
+        self.pub_key.clone()
+    }
+
+    // Responds to any u coordinate with (message, MAC under shared key),
+    // never checking that u is on the curve
+    pub fn respond(&self, u: &BigUint) -> (Vec<u8>, Vec<u8>) {
+        self.queries.set(self.queries.get() + 1);
+        let k = self.ecdh.gen_session_key(u, &self.x);
+        (MESSAGE.to_vec(), hmac_sha1(&k.to_bytes_be(), MESSAGE))
+    }
+
+    pub fn is_secret(&self, x: &BigUint) -> bool {
+        self.x == *x
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl Default for Bob {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Attacker
+// Curve & its quadratic twist have 2p + 2 points in total
+fn twist_order(ecdh: &MontgomeryECDH) -> BigUint {
+    (&ecdh.curve.p + 1_u8) * 2_u8 - ECDH::curve_order_59()
+}
+
+// Random u coordinate of a twist point of order m (product of distinct
+// primes dividing twist order)
+fn twist_point_of_order(
+    curve: &MontgomeryCurve,
+    twist_order: &BigUint,
+    m: &BigUint,
+    primes: &[u64],
+) -> BigUint {
+    loop {
+        let u = rand::thread_rng().gen_biguint_below(&curve.p);
+        if curve.is_on_curve(&u) {
+            continue;
+        }
+        let point = curve.ladder(&u, &(twist_order / m));
+        if primes
+            .iter()
+            .all(|&r| !curve.ladder(&point, &(m / r)).is_zero())
+        {
+            return point;
+        }
+    }
+}
+
+// k in [0, r / 2] with Bob's secret = +-k mod r, by walking k * P with
+// differential additions: u(P + Q) u(P - Q) = (u(P) u(Q) - 1)^2 /
+// (u(P) - u(Q))^2. Signs can't be told apart with u coordinates only.
+fn secret_mod_up_to_sign(
+    bob: &Bob,
+    curve: &MontgomeryCurve,
+    u: &BigUint,
+    r: u64,
+) -> Result<BigUint> {
+    let p = &curve.p;
+    let (msg, mac) = bob.respond(u);
+    let matches = |u_k: &BigUint| hmac_sha1(&u_k.to_bytes_be(), &msg) == mac;
+
+    // Ladder gives zero for infinity (k = 0)
+    if matches(&BigUint::zero()) {
+        return Ok(BigUint::zero());
+    }
+    let (mut prev, mut curr) = (u.clone(), curve.ladder(u, &BigUint::from(2_u8)));
+    if matches(&prev) {
+        return Ok(BigUint::one());
+    }
+    for k in 2..=r / 2 {
+        if matches(&curr) {
+            return Ok(BigUint::from(k));
+        }
+        let num = sub_mod(&(&curr * u % p), &BigUint::one(), p);
+        let denom = sub_mod(&curr, u, p);
+        let next = &num * &num % p * inv_mod(&(&denom * &denom % p * &prev % p), p) % p;
+        (prev, curr) = (curr, next);
+    }
+    Err(CryptoError::AttackFailed(
+        "no key matched the MAC".to_owned(),
+    ))
+}
+
+// Combines x = +-n mod m & x = +-k mod r into x = +-n' mod mr, telling
+// apart the two possibilities with a query on a twist point of order mr
+fn combine_residues(
+    bob: &Bob,
+    twist_order: &BigUint,
+    primes: &[u64],
+    (n, m): (&BigUint, &BigUint),
+    (k, r): (&BigUint, u64),
+) -> BigUint {
+    let curve = &bob.ecdh.curve;
+    let r_big = BigUint::from(r);
+    let c1 = crt(&[(n.clone(), m.clone()), (k.clone(), r_big.clone())]).0;
+    let c2 = crt(&[
+        (n.clone(), m.clone()),
+        ((&r_big - k) % &r_big, r_big.clone()),
+    ])
+    .0;
+
+    let u = twist_point_of_order(curve, twist_order, &(m * &r_big), primes);
+    let (msg, mac) = bob.respond(&u);
+    if hmac_sha1(&curve.ladder(&u, &c1).to_bytes_be(), &msg) == mac {
+        c1
+    } else {
+        c2
+    }
+}
+
+// Points of the Weierstrass form, for the #58 kangaroo. Jump picked by x
+// coordinate.
+impl Group for WeierstrassCurve {
+    type Element = Point;
+
+    fn mul(&self, a: &Point, b: &Point) -> Point {
+        self.add(a, b)
+    }
+
+    fn pow(&self, a: &Point, e: &BigUint) -> Point {
+        self.scalar_mul(a, e)
+    }
+
+    fn index(&self, a: &Point, k: usize) -> usize {
+        match a {
+            Point::Infinity => 0,
+            Point::Affine(x, _) => (x % k).to_usize().unwrap(),
+        }
+    }
+}
+
+// Bob's secret, known to be below secret_bound, using twist subgroups of
+// prime order below factor_bound
+pub fn recover_secret(bob: &Bob, factor_bound: u64, secret_bound: &BigUint) -> Result<BigUint> {
+    let curve = &bob.ecdh.curve;
+    let twist_order = twist_order(&bob.ecdh);
+
+    // Order 2 point (u = 0) gives zero to Bob whatever his secret is
+    let mut primes: Vec<u64> = Vec::new();
+    let (mut n, mut m) = (BigUint::zero(), BigUint::one());
+    for r in small_factors(&twist_order, factor_bound) {
+        if r == 2 {
+            continue;
+        }
+        let u = twist_point_of_order(curve, &twist_order, &r.into(), &[r]);
+        let k = secret_mod_up_to_sign(bob, curve, &u, r)?;
+        primes.push(r);
+        n = if m.is_one() {
+            k
+        } else {
+            combine_residues(bob, &twist_order, &primes, (&n, &m), (&k, r))
+        };
+        m *= r;
+    }
+
+    // Secret x = +-n mod m, so x = n' + i * m for i in [0, secret_bound / m]
+    // with n' = n or -n. Bob's public key gives Y = +-x * G on the
+    // Weierstrass form, and kangaroo finds i on Y - n' * G = i * (m * G)
+    // for the right choice of both signs.
+    let ecdh = ECDH::challenge_59();
+    let ec = &ecdh.curve;
+    let x = (bob.pub_key() + U_OFFSET) % &ec.p;
+    let y = Point::Affine(x.clone(), sqrt_mod(&ec.rhs(&x), &ec.p).unwrap());
+    let g_m = ec.scalar_mul(&ecdh.g, &m);
+    let b = secret_bound / &m;
+
+    let mut candidates = Vec::new();
+    for y in [y.clone(), ec.neg(&y)] {
+        for n in [n.clone(), (&m - &n) % &m] {
+            let target = ec.add(&y, &ec.neg(&ec.scalar_mul(&ecdh.g, &n)));
+            candidates.push((n, target));
+        }
+    }
+
+    // Kangaroo misses with small probability; retry with another jump
+    // function if it does
+    let (k, n_jumps) = jump_params(&b);
+    let a = BigUint::zero();
+    (k..k + KANGAROO_TRIES)
+        .find_map(|k| {
+            candidates.iter().find_map(|(n, target)| {
+                kangaroo(ec, &g_m, target, (&a, &b), k, &n_jumps).map(|i| n + i * &m)
+            })
+        })
+        .ok_or_else(|| CryptoError::AttackFailed("kangaroo missed".to_owned()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c60() {
+        // Full attack (factors up to 2^22, leaving a 2^40 interval) takes
+        // minutes with BigUint arithmetic. Twist factors below 2^19 leave
+        // secret known up to sign modulo about 2^64, and a secret below
+        // 2^90 then leaves a 2^26 interval for the kangaroo.
+        let secret_bound = BigUint::one() << 90;
+        let x = rand::thread_rng().gen_biguint_below(&secret_bound);
+        let bob = Bob::with_secret(&x);
+
+        let recovered = recover_secret(&bob, 1 << 19, &secret_bound).unwrap();
+        assert!(bob.is_secret(&recovered));
+    }
+
+    #[test]
+    #[ignore = "takes about 10 minutes"]
+    fn test_c60_full() {
+        // Random secret below q, with twist factors up to 2^22 leaving a
+        // 2^40 interval for the kangaroo
+        let bob = Bob::new();
+        let q = bob.ecdh.q.clone();
+        let recovered = recover_secret(&bob, 1 << 22, &q).unwrap();
+        assert!(bob.is_secret(&recovered));
+    }
+}
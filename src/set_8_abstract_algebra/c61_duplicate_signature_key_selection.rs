@@ -0,0 +1,207 @@
+use crate::error::Result;
+use crate::set_8_abstract_algebra::c57_diffie_hellman_small_subgroup_confinement::crt;
+use crate::utils::dsa::Signature;
+use crate::utils::ec::Point;
+use crate::utils::ecdsa::ECDSA;
+use crate::utils::prime::is_probable_prime;
+use crate::utils::rsa::{pad_pkcs1_v15_sign, RSA};
+use crate::utils::sha::sha1_hash;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+
+// Factors of p - 1 for the smooth primes are taken from this range
+const MIN_FACTOR: u64 = 1 << 8;
+const MAX_FACTOR: u64 = 1 << 12;
+
+// Attacker
+// New domain parameters & key pair (ECDSA, d', Q') under which sig on msg,
+// made by owner of pub_key, verifies. With R = u1 * G + u2 * Q (whose x
+// is r), any d' and G' = (u1 + u2 * d')^-1 * R give the same R.
+pub fn ecdsa_dsks(
+    ecdsa: &ECDSA,
+    msg: &[u8],
+    sig: &Signature,
+    pub_key: &Point,
+) -> (ECDSA, BigUint, Point) {
+    let (curve, q) = (&ecdsa.curve, &ecdsa.q);
+    let w = sig.s.modinv(q).unwrap();
+    let u1 = ecdsa.hash(msg) * &w % q;
+    let u2 = &sig.r * &w % q;
+    let r_point = curve.add(
+        &curve.scalar_mul(&ecdsa.g, &u1),
+        &curve.scalar_mul(pub_key, &u2),
+    );
+
+    loop {
+        let d = rand::thread_rng().gen_biguint_range(&BigUint::one(), q);
+        let t = (&u1 + &u2 * &d) % q;
+        if let Some(t_inv) = t.modinv(q) {
+            let g = curve.scalar_mul(&r_point, &t_inv);
+            let forged = ECDSA::new(curve, &g, q);
+            let forged_key = curve.scalar_mul(&g, &d);
+            return (forged, d, forged_key);
+        }
+    }
+}
+
+// Primes below n (sieve of Eratosthenes)
+fn primes_below(n: u64) -> Vec<u64> {
+    let mut is_prime = vec![true; n as usize];
+    for i in 2..n as usize {
+        if is_prime[i] {
+            for j in (i * i..n as usize).step_by(i) {
+                is_prime[j] = false;
+            }
+        }
+    }
+    (2..n).filter(|&i| is_prime[i as usize]).collect()
+}
+
+// Prime p with p - 1 = 2 * (distinct small primes not in used), s a
+// primitive root & m a non-residue mod p (so log of m to base s is odd).
+// Returns (p, prime factors of p - 1)
+fn smooth_prime(
+    bits: u64,
+    s: &BigUint,
+    m: &BigUint,
+    used: &mut HashSet<u64>,
+) -> (BigUint, Vec<u64>) {
+    let mut rng = rand::thread_rng();
+    let primes: Vec<u64> = primes_below(2 * MAX_FACTOR)
+        .into_iter()
+        .filter(|f| !used.contains(f))
+        .collect();
+    let factor_range: Vec<u64> = primes
+        .iter()
+        .cloned()
+        .filter(|f| (MIN_FACTOR..MAX_FACTOR).contains(f))
+        .collect();
+
+    loop {
+        let mut factors = vec![2_u64];
+        let mut product = BigUint::from(2_u8);
+        while product.bits() + 12 < bits {
+            let f = *factor_range.choose(&mut rng).unwrap();
+            if !factors.contains(&f) {
+                factors.push(f);
+                product *= f;
+            }
+        }
+
+        // Last factor picked to give p exactly `bits` bits
+        let lo = ((BigUint::one() << (bits - 1)) + &product - 1_u8) / &product;
+        let hi = (BigUint::one() << bits) / &product;
+        let last: Vec<u64> = primes
+            .iter()
+            .cloned()
+            .filter(|&f| BigUint::from(f) >= lo && BigUint::from(f) < hi && !factors.contains(&f))
+            .collect();
+        let f = match last.choose(&mut rng) {
+            Some(&f) => f,
+            None => continue,
+        };
+        factors.push(f);
+        product *= f;
+
+        let p = &product + 1_u8;
+        if !is_probable_prime(&p, 40) || m.modpow(&(&product >> 1), &p).is_one() {
+            continue;
+        }
+        if factors
+            .iter()
+            .all(|&f| !s.modpow(&(&product / f), &p).is_one())
+        {
+            used.extend(factors.iter().skip(1));
+            return (p, factors);
+        }
+    }
+}
+
+// Pohlig-Hellman: x with g^x = y (mod p), for primitive root g & p - 1
+// with distinct prime factors, brute forcing x mod each factor
+fn discrete_log(g: &BigUint, y: &BigUint, p: &BigUint, factors: &[u64]) -> BigUint {
+    let residues: Vec<(BigUint, BigUint)> = factors
+        .iter()
+        .map(|&f| {
+            let exp = (p - 1_u8) / f;
+            let (g_f, y_f) = (g.modpow(&exp, p), y.modpow(&exp, p));
+            let mut power = BigUint::one();
+            let x = (0..f)
+                .find(|_| {
+                    let found = power == y_f;
+                    power = &power * &g_f % p;
+                    found
+                })
+                .unwrap();
+            (BigUint::from(x), BigUint::from(f))
+        })
+        .collect();
+    crt(&residues).0
+}
+
+// New RSA key pair under which sig on msg verifies. Primes with smooth
+// p - 1 make discrete log tractable, to find e' with sig^e' = pad(msg)
+// modulo each of them.
+pub fn rsa_dsks(rsa: &RSA, msg: &[u8], sig: &BigUint) -> Result<RSA> {
+    let m = BigUint::from_bytes_be(&pad_pkcs1_v15_sign(&sha1_hash(msg), rsa.k())?);
+    let bits = rsa.n.bits();
+    loop {
+        // Same size modulus (so same padding) larger than the original one
+        let mut used = HashSet::new();
+        let (p, p_factors) = smooth_prime(bits / 2, sig, &m, &mut used);
+        let (q, q_factors) = smooth_prime(bits - bits / 2, sig, &m, &mut used);
+        let n = &p * &q;
+        if n.bits() != bits || n <= rsa.n {
+            continue;
+        }
+
+        // Only common factor of p - 1 & q - 1 is 2, and both logs are odd,
+        // so they combine
+        let e_p = discrete_log(sig, &m, &p, &p_factors);
+        let e_q = discrete_log(sig, &m, &q, &q_factors);
+        let half_q = (&q - 1_u8) >> 1;
+        let e = crt(&[(e_p, &p - 1_u8), (e_q % &half_q, half_q)]).0;
+
+        let phi = (&p - 1_u8) * (&q - 1_u8);
+        if let Some(d) = e.modinv(&phi) {
+            return Ok(RSA::from_parts(&e, &n, &d));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_c61_ecdsa() {
+        let msg = b"hi mom";
+        let ecdsa = ECDSA::challenge_59();
+        let (d, pub_key) = ecdsa.gen_key_pair();
+        let sig = ecdsa.sign(msg, &d);
+
+        let (forged, forged_d, forged_key) = ecdsa_dsks(&ecdsa, msg, &sig, &pub_key);
+        assert!(forged.verify(msg, &sig, &forged_key));
+        assert_ne!(forged_key, pub_key);
+
+        // Forged key pair works for signing too
+        let other_sig = forged.sign(b"hi dad", &forged_d);
+        assert!(forged.verify(b"hi dad", &other_sig, &forged_key));
+    }
+
+    #[test]
+    fn test_c61_rsa() {
+        let msg = b"hi mom";
+        let rsa = RSA::new(512);
+        let sig = rsa.sign(msg).unwrap();
+        assert!(rsa.verify(msg, &sig));
+
+        let forged = rsa_dsks(&rsa, msg, &sig).unwrap();
+        assert_ne!(forged.n, rsa.n);
+        assert!(forged.verify(msg, &sig));
+        assert!(!forged.verify(b"hi dad", &sig));
+        assert_eq!(forged.sign(msg).unwrap(), sig);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::utils::dsa::Signature;
+use crate::utils::ec::Point;
+use crate::utils::ecdsa::ECDSA;
+use crate::utils::lll::{lll, to_rational, Vector};
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+use std::cell::Cell;
+
+// Low bits of every nonce that are zero
+const BIASED_BITS: u64 = 8;
+
+pub struct Signer {
+    pub ecdsa: ECDSA,
+    d: BigUint,
+    pub_key: Point,
+    queries: Cell<usize>,
+}
+
+impl Signer {
+    pub fn new() -> Self {
+        let ecdsa = ECDSA::challenge_59();
+        let (d, pub_key) = ecdsa.gen_key_pair();
+        Self {
+            ecdsa,
+            d,
+            pub_key,
+            queries: Cell::new(0),
+        }
+    }
+
+    pub fn pub_key(&self) -> Point {
+        self.pub_key.clone()
+    }
+
+    // Signs with nonces whose low BIASED_BITS bits are zero
+    pub fn sign(&self, msg: &[u8]) -> Signature {
+        self.queries.set(self.queries.get() + 1);
+        let bound = &self.ecdsa.q >> BIASED_BITS;
+        loop {
+            let k = rand::thread_rng().gen_biguint_range(&BigUint::one(), &bound) << BIASED_BITS;
+            if let Some(sig) = self.ecdsa.sign_with_k(msg, &self.d, &k) {
+                if !sig.r.is_zero() && !sig.s.is_zero() {
+                    return sig;
+                }
+            }
+        }
+    }
+
+    pub fn is_secret(&self, d: &BigUint) -> bool {
+        self.d == *d
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl Default for Signer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Attacker
+fn to_int(x: &BigUint) -> BigInt {
+    BigInt::from_biguint(Sign::Plus, x.clone())
+}
+
+// With k = 2^l * b, s = (H + d * r) / k gives b = d * t - u (mod q) for
+// t = r / (s * 2^l) & u = H / (-s * 2^l), where b < q / 2^l: an instance of
+// the hidden number problem. Rows of the basis are q * e_i for each
+// signature, then (t_1, ..., t_n, ct, 0) & (u_1, ..., u_n, 0, cu) with ct =
+// 1 / 2^l & cu = q / 2^l. The short vector (b_1, ..., b_n, d * ct, -cu)
+// is in the lattice, and reduction finds it.
+fn hnp_basis(ecdsa: &ECDSA, sigs: &[(Vec<u8>, Signature)]) -> Vec<Vector> {
+    let q = &ecdsa.q;
+    let n = sigs.len();
+    let shift = BigUint::one() << BIASED_BITS;
+    let mut basis: Vec<Vector> = (0..n)
+        .map(|i| {
+            let mut row = vec![BigInt::zero(); n + 2];
+            row[i] = to_int(q);
+            to_rational(&row)
+        })
+        .collect();
+
+    let mut t_row = vec![BigRational::zero(); n + 2];
+    let mut u_row = vec![BigRational::zero(); n + 2];
+    for (i, (msg, sig)) in sigs.iter().enumerate() {
+        let denom_inv = (&sig.s * &shift % q).modinv(q).unwrap();
+        let t = &sig.r * &denom_inv % q;
+        let u = (q - ecdsa.hash(msg) % q) * &denom_inv % q;
+        t_row[i] = BigRational::from_integer(to_int(&t));
+        u_row[i] = BigRational::from_integer(to_int(&u));
+    }
+    let shift = to_int(&shift);
+    t_row[n] = BigRational::new(BigInt::one(), shift.clone());
+    u_row[n + 1] = BigRational::new(to_int(q), shift);
+    basis.push(t_row);
+    basis.push(u_row);
+    basis
+}
+
+// Signer's private key from signatures (with their messages) made with
+// biased nonces
+pub fn recover_key(
+    ecdsa: &ECDSA,
+    pub_key: &Point,
+    sigs: &[(Vec<u8>, Signature)],
+) -> Option<BigUint> {
+    let n = sigs.len();
+    let q = to_int(&ecdsa.q);
+    let cu = BigRational::new(q.clone(), BigInt::one() << BIASED_BITS);
+    let delta = BigRational::new(BigInt::from(99), BigInt::from(100));
+
+    lll(&hnp_basis(ecdsa, sigs), &delta)?
+        .iter()
+        .filter(|row| row[n + 1].abs() == cu)
+        .find_map(|row| {
+            // Row is +-(b_1, ..., b_n, d * ct, -cu)
+            let d = &row[n] * BigRational::from_integer(BigInt::one() << BIASED_BITS);
+            if !d.is_integer() {
+                return None;
+            }
+            let mut d = d.to_integer();
+            if row[n + 1].is_positive() {
+                d = -d;
+            }
+            let d = ((d % &q) + &q) % &q;
+            let d = d.to_biguint().unwrap();
+            (ecdsa.curve.scalar_mul(&ecdsa.g, &d) == *pub_key).then_some(d)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_c62() {
+        let signer = Signer::new();
+        let sigs: Vec<(Vec<u8>, Signature)> = (0..22)
+            .map(|_| {
+                let msg: Vec<u8> = (0..16).map(|_| rand::thread_rng().gen()).collect();
+                let sig = signer.sign(&msg);
+                (msg, sig)
+            })
+            .collect();
+
+        let d = recover_key(&signer.ecdsa, &signer.pub_key(), &sigs).unwrap();
+        assert!(signer.is_secret(&d));
+    }
+}
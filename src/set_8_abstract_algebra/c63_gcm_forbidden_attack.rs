@@ -0,0 +1,274 @@
+use crate::error::Result;
+use crate::utils::aes::{aes128_gcm_decrypt, aes128_gcm_encrypt};
+use crate::utils::gf128::{ghash_blocks, Gf128};
+use crate::utils::gf128_poly::Poly;
+use num_bigint::BigUint;
+use num_traits::One;
+use rand::Rng;
+
+// Encrypts with a fixed key & nonce, the nonce reused on every message
+pub struct Alice {
+    key: Vec<u8>,
+    iv: Vec<u8>,
+}
+
+impl Alice {
+    pub fn new() -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            key: (0..16).map(|_| rng.gen()).collect(),
+            iv: (0..12).map(|_| rng.gen()).collect(),
+        }
+    }
+
+    // Returns (ciphertext, tag)
+    pub fn encrypt(&self, msg: &[u8], aad: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        aes128_gcm_encrypt(msg, &self.key, &self.iv, aad)
+    }
+
+    pub fn decrypt(&self, ciphertext: &[u8], aad: &[u8], tag: &[u8]) -> Result<Vec<u8>> {
+        aes128_gcm_decrypt(ciphertext, &self.key, &self.iv, aad, tag)
+    }
+}
+
+impl Default for Alice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Attacker
+// Message authenticated under a (possibly repeated) nonce
+pub struct Authenticated {
+    pub aad: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub tag: Vec<u8>,
+}
+
+// tag = b_1 * H^m + ... + b_m * H + s for GHASH blocks b_i & s = E(J0), so
+// the polynomial b_1 * x^m + ... + b_m * x + tag takes value s at H
+fn tag_poly(msg: &Authenticated) -> Poly {
+    let blocks = ghash_blocks(&msg.aad, &msg.ciphertext);
+    let coeffs: Vec<Gf128> = std::iter::once(Gf128::from_block(&msg.tag))
+        .chain(blocks.into_iter().rev())
+        .collect();
+    Poly::new(&coeffs)
+}
+
+// Square root of a polynomial with only even powers of x (squaring is the
+// Frobenius map, so c^(1/2) = c^(2^127))
+fn sqrt_poly(f: &Poly) -> Poly {
+    let coeffs: Vec<Gf128> = f
+        .coeffs()
+        .iter()
+        .step_by(2)
+        .map(|c| c.pow(1 << 127))
+        .collect();
+    Poly::new(&coeffs)
+}
+
+// Square-free factorization: (square-free factor, multiplicity) pairs of a
+// polynomial, made monic. The zero polynomial has no factorization, so it
+// gives no pairs.
+pub fn square_free(f: &Poly) -> Vec<(Poly, usize)> {
+    if f.is_zero() {
+        return Vec::new();
+    }
+    let f = f.monic();
+    if f.degree() == Some(0) {
+        return Vec::new();
+    }
+    let squared = |factors: Vec<(Poly, usize)>| factors.into_iter().map(|(g, m)| (g, 2 * m));
+    let d = f.derivative();
+    if d.is_zero() {
+        return squared(square_free(&sqrt_poly(&f))).collect();
+    }
+
+    // Factors of multiplicity i are in w, c holds the rest with one
+    // multiplicity less, or multiplicities divisible by 2
+    let mut factors = Vec::new();
+    let mut c = f.gcd(&d);
+    let mut w = f.divmod(&c).0;
+    let mut i = 1;
+    while w != Poly::one() {
+        let y = w.gcd(&c);
+        let factor = w.divmod(&y).0;
+        if factor != Poly::one() {
+            factors.push((factor, i));
+        }
+        c = c.divmod(&y).0;
+        w = y;
+        i += 1;
+    }
+    if c != Poly::one() {
+        factors.extend(squared(square_free(&sqrt_poly(&c))));
+    }
+    factors
+}
+
+// Distinct-degree factorization of a monic square-free polynomial: pairs
+// of (product of all irreducible factors of degree d, d). Irreducibles of
+// degree dividing i are the factors of x^(q^i) - x, q = 2^128.
+pub fn distinct_degree(f: &Poly) -> Vec<(Poly, usize)> {
+    let q = BigUint::one() << 128;
+    let mut factors = Vec::new();
+    let mut f = f.clone();
+    let mut h = Poly::x();
+    let mut i = 1;
+    while f.degree().unwrap() >= 2 * i {
+        h = h.modexp(&q, &f);
+        let g = f.gcd(&h.add(&Poly::x()));
+        if g != Poly::one() {
+            f = f.divmod(&g).0;
+            h = h.rem(&f);
+            factors.push((g, i));
+        }
+        i += 1;
+    }
+    if f != Poly::one() {
+        let d = f.degree().unwrap();
+        factors.push((f, d));
+    }
+    factors
+}
+
+// Equal-degree (Cantor-Zassenhaus) factorization of a monic square-free
+// polynomial whose irreducible factors all have degree d. In
+// characteristic 2, the trace map a + a^2 + ... + a^(2^(128d - 1)) of a
+// random a lands in GF(2) modulo each factor, so its gcd with f splits f.
+pub fn equal_degree(f: &Poly, d: usize) -> Vec<Poly> {
+    let n = f.degree().unwrap() / d;
+    let mut rng = rand::thread_rng();
+    let mut factors = vec![f.clone()];
+    while factors.len() < n {
+        let coeffs: Vec<Gf128> = (0..f.degree().unwrap()).map(|_| Gf128(rng.gen())).collect();
+        let mut a = Poly::new(&coeffs);
+        let mut trace = a.clone();
+        for _ in 1..128 * d {
+            a = a.mul(&a).rem(f);
+            trace = trace.add(&a);
+        }
+
+        factors = factors
+            .into_iter()
+            .flat_map(|u| {
+                let g = u.gcd(&trace);
+                if u.degree() == Some(d) || g == Poly::one() || g == u {
+                    vec![u]
+                } else {
+                    let v = u.divmod(&g).0;
+                    vec![g, v]
+                }
+            })
+            .collect();
+    }
+    factors
+}
+
+// Roots of a polynomial: constant terms of its (monic) degree 1 factors.
+// Every element is a root of the zero polynomial, so it gives none.
+pub fn roots(f: &Poly) -> Vec<Gf128> {
+    square_free(f)
+        .iter()
+        .flat_map(|(g, _)| distinct_degree(g))
+        .filter(|(_, d)| *d == 1)
+        .flat_map(|(g, _)| equal_degree(&g, 1))
+        .map(|g| g.coeffs()[0])
+        .collect()
+}
+
+// Candidates for the authentication key H from two messages under the same
+// nonce: roots of the difference of their tag polynomials, s cancelling out.
+// Identical messages give no candidates.
+pub fn key_candidates(msg1: &Authenticated, msg2: &Authenticated) -> Vec<Gf128> {
+    roots(&tag_poly(msg1).add(&tag_poly(msg2)))
+}
+
+// Tag on new aad & ciphertext under the nonce of msg, if h is the key
+pub fn forge_tag(h: Gf128, msg: &Authenticated, aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let s = tag_poly(msg).eval(h);
+    let forged = Authenticated {
+        aad: aad.to_vec(),
+        ciphertext: ciphertext.to_vec(),
+        tag: vec![0; 16],
+    };
+    (tag_poly(&forged).eval(h) + s).to_block()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::aes::aes128_encrypt_block;
+    use crate::utils::bitwise::xor_bytes;
+
+    #[test]
+    fn test_c63_factorization() {
+        let (a, b, c) = (Gf128(5), Gf128(0xcafe << 80), Gf128(u128::MAX));
+        let linear = |r: Gf128| Poly::new(&[r, Gf128::one()]);
+        let (xa, xb, xc) = (linear(a), linear(b), linear(c));
+        let f = xa.mul(&xa).mul(&xb).mul(&xc);
+
+        let mut factors = square_free(&f);
+        factors.sort_by_key(|(_, m)| *m);
+        assert_eq!(factors, vec![(xb.mul(&xc), 1), (xa.clone(), 2)]);
+        assert_eq!(distinct_degree(&xb.mul(&xc)), vec![(xb.mul(&xc), 1)]);
+
+        // x^2 + x + e has no roots when e has trace e + e^2 + ... + e^(2^127)
+        // equal to 1
+        let trace = |e: Gf128| {
+            (0..128)
+                .map(|i| e.pow(1 << i))
+                .fold(Gf128::zero(), |t, x| t + x)
+        };
+        let e = (1..)
+            .map(Gf128)
+            .find(|&e| trace(e) == Gf128::one())
+            .unwrap();
+        let g = xb
+            .mul(&xc)
+            .mul(&Poly::new(&[e, Gf128::one(), Gf128::one()]));
+        let found = roots(&g);
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&b) && found.contains(&c));
+        assert_eq!(square_free(&xa.mul(&xa)), vec![(xa, 2)]);
+        assert!(square_free(&Poly::zero()).is_empty());
+        assert!(roots(&Poly::new(&[Gf128::zero()])).is_empty());
+    }
+
+    #[test]
+    fn test_c63() {
+        let alice = Alice::new();
+        let mut messages = Vec::new();
+        for (msg, aad) in [
+            (&b"attack at dawn, from the north"[..], &b"header"[..]),
+            (
+                &b"retreat at dusk, to the south-west hills"[..],
+                &b"other header"[..],
+            ),
+        ] {
+            let (ciphertext, tag) = alice.encrypt(msg, aad).unwrap();
+            messages.push(Authenticated {
+                aad: aad.to_vec(),
+                ciphertext,
+                tag,
+            });
+        }
+
+        let candidates = key_candidates(&messages[0], &messages[1]);
+        let h = Gf128::from_block(&aes128_encrypt_block(&[0; 16], &alice.key).unwrap());
+        assert!(candidates.contains(&h));
+
+        // A replayed message cancels out completely
+        assert!(key_candidates(&messages[0], &messages[0]).is_empty());
+
+        // CTR keystream is known from a known plaintext, so any plaintext can
+        // be encrypted; the right H then gives a tag Alice accepts
+        let keystream = xor_bytes(&messages[0].ciphertext, b"attack at dawn, from the north");
+        let ciphertext = xor_bytes(&keystream, b"attack at noon, from the south");
+        let forged = candidates.iter().find_map(|&h| {
+            let tag = forge_tag(h, &messages[1], b"forged", &ciphertext);
+            alice.decrypt(&ciphertext, b"forged", &tag).ok()
+        });
+        assert_eq!(forged, Some(b"attack at noon, from the south".to_vec()));
+    }
+}
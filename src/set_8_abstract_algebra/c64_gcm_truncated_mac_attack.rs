@@ -0,0 +1,175 @@
+use crate::error::Result;
+use crate::utils::aes::{aes128_gcm_encrypt, aes128_gcm_tag};
+use crate::utils::bitwise::xor_bytes;
+use crate::utils::gf128::Gf128;
+use crate::utils::gf2_matrix::BitMatrix;
+use rand::Rng;
+use std::cell::Cell;
+
+// GCM with tags truncated to their first tag_len bytes
+pub struct Alice {
+    key: Vec<u8>,
+    tag_len: usize,
+    queries: Cell<usize>,
+}
+
+impl Alice {
+    pub fn new(tag_len: usize) -> Self {
+        Self {
+            key: (0..16).map(|_| rand::thread_rng().gen()).collect(),
+            tag_len,
+            queries: Cell::new(0),
+        }
+    }
+
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    // Returns (iv, ciphertext, truncated tag), under a fresh nonce
+    pub fn encrypt(&self, msg: &[u8]) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let iv: Vec<u8> = (0..12).map(|_| rand::thread_rng().gen()).collect();
+        let (ciphertext, tag) = aes128_gcm_encrypt(msg, &self.key, &iv, &[])?;
+        Ok((iv, ciphertext, tag[..self.tag_len].to_vec()))
+    }
+
+    pub fn verify(&self, iv: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<bool> {
+        self.queries.set(self.queries.get() + 1);
+        Ok(aes128_gcm_tag(ciphertext, &self.key, iv, &[])?[..self.tag_len] == *tag)
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+// Attacker
+// Index of the ciphertext block whose GHASH coefficient is H^(2^i), in a
+// ciphertext of 2^n blocks (lengths block comes last, with coefficient H)
+fn block_index(n: usize, i: usize) -> usize {
+    (1 << n) + 1 - (1 << i)
+}
+
+// Adds error e_i to the block with coefficient H^(2^(i + 1)). Lengths are
+// unchanged, so the tag changes by (sum of e_i * H^(2^(i + 1))) only.
+fn apply_errors(ciphertext: &[u8], errors: &[Gf128], n: usize) -> Vec<u8> {
+    let mut forged = ciphertext.to_vec();
+    for (i, e) in errors.iter().enumerate() {
+        let start = 16 * block_index(n, i + 1);
+        let block = xor_bytes(&forged[start..start + 16], &e.to_block());
+        forged[start..start + 16].copy_from_slice(&block);
+    }
+    forged
+}
+
+// Ad = sum of M(e_i) * Sq^(i + 1), the tag difference being Ad * H:
+// squaring is linear, so is each e_i * H^(2^(i + 1))
+fn error_matrix(errors: &[Gf128], squarings: &[BitMatrix]) -> BitMatrix {
+    errors
+        .iter()
+        .zip(squarings)
+        .fold(BitMatrix::zero(128, 128), |acc, (e, sq)| {
+            acc.add(&e.mul_matrix().mul(sq))
+        })
+}
+
+// Ferguson's attack: errors in the blocks of a 2^n block ciphertext with
+// coefficients H^2, H^4, ..., H^(2^n) are chosen from the kernel of the
+// map taking them to the first rows of Ad * X, where the columns of X span
+// what's left of the candidates for H. Forgeries then succeed with
+// probability 2^-(remaining tag bits), and each gives linear equations on
+// H from the rows of Ad * X that have to vanish. Those shrink X until H is
+// the only candidate.
+pub fn recover_key(alice: &Alice, n: usize) -> Result<Gf128> {
+    let tag_bits = alice.tag_len() * 8;
+    let msg: Vec<u8> = (0..16 << n).map(|_| rand::thread_rng().gen()).collect();
+    let (iv, ciphertext, tag) = alice.encrypt(&msg)?;
+
+    // Sq^i for i in 1..=n, & M(x^b) for each bit b of an error block
+    let sq = Gf128::square_matrix();
+    let squarings: Vec<BitMatrix> = std::iter::successors(Some(sq.clone()), |m| Some(sq.mul(m)))
+        .take(n)
+        .collect();
+    let mul_x: Vec<BitMatrix> = (0..128).map(|b| Gf128::x_pow(b).mul_matrix()).collect();
+
+    let mut basis = BitMatrix::identity(128);
+    while basis.cols() > 1 {
+        // As many zero rows as leave a non-trivial kernel, short of the
+        // whole tag (which would reveal nothing)
+        let k = basis.cols();
+        let zero_rows = (tag_bits - 1).min((n * 128 - 1) / k);
+        let mut t = BitMatrix::zero(zero_rows * k, n * 128);
+        for (i, sq) in squarings.iter().enumerate() {
+            let sq_basis = sq.mul(&basis);
+            for (b, m) in mul_x.iter().enumerate() {
+                let column = m.top_rows(zero_rows).mul(&sq_basis);
+                for r in 0..zero_rows {
+                    for c in (0..k).filter(|&c| column.get(r, c)) {
+                        t.set(r * k + c, i * 128 + b, true);
+                    }
+                }
+            }
+        }
+        let kernel = t.kernel();
+
+        let errors = loop {
+            let bits = kernel.random_combination();
+            let errors: Vec<Gf128> = bits
+                .chunks(128)
+                .map(|block| {
+                    (0..128)
+                        .filter(|&j| block[j])
+                        .fold(Gf128::zero(), |acc, j| acc + Gf128::x_pow(j))
+                })
+                .collect();
+            if errors.iter().all(|e| e.is_zero()) {
+                continue;
+            }
+            if alice.verify(&iv, &apply_errors(&ciphertext, &errors, n), &tag)? {
+                break errors;
+            }
+        };
+
+        // H = X * y for y in the kernel of the first rows of Ad * X
+        let equations = error_matrix(&errors, &squarings)
+            .top_rows(tag_bits)
+            .mul(&basis);
+        basis = basis.mul(&equations.kernel().transpose());
+    }
+    Ok(Gf128::from_column(&basis, 0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::aes::aes128_encrypt_block;
+    use crate::utils::gf128::ghash;
+
+    #[test]
+    fn test_c64() {
+        // Challenge has 32 bit tags & 2^17 block messages, needing about
+        // 2^16 forgery attempts for the first equations. 16 bit tags & 2^9
+        // blocks need about 2^8.
+        let alice = Alice::new(2);
+        let h = recover_key(&alice, 9).unwrap();
+        let key_block = aes128_encrypt_block(&[0; 16], &alice.key).unwrap();
+        assert_eq!(h, Gf128::from_block(&key_block));
+
+        // Knowing H, any change to a ciphertext comes with the matching tag
+        let (iv, ciphertext, tag) = alice.encrypt(b"attack at dawn, from the north").unwrap();
+        let forged = xor_bytes(&ciphertext, b"\0\0\0\0\0\0\0\0\0\0noon");
+        let delta = ghash(h, &[], &ciphertext) + ghash(h, &[], &forged);
+        let forged_tag = xor_bytes(&tag, &delta.to_block());
+        assert!(alice.verify(&iv, &forged, &forged_tag).unwrap());
+    }
+
+    #[test]
+    #[ignore = "takes about 20 minutes"]
+    fn test_c64_32_bit_tags() {
+        // Challenge parameters: 32 bit tags & 2^17 block messages
+        let alice = Alice::new(4);
+        let h = recover_key(&alice, 17).unwrap();
+        let key_block = aes128_encrypt_block(&[0; 16], &alice.key).unwrap();
+        assert_eq!(h, Gf128::from_block(&key_block));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod c57_diffie_hellman_small_subgroup_confinement;
+pub mod c58_pollard_kangaroo;
+pub mod c59_ecdh_invalid_curve_attack;
+pub mod c60_single_coordinate_ladders_twist_attack;
+pub mod c61_duplicate_signature_key_selection;
+pub mod c62_biased_nonce_ecdsa_key_recovery;
+pub mod c63_gcm_forbidden_attack;
+pub mod c64_gcm_truncated_mac_attack;
@@ -1,6 +1,18 @@
 pub mod aes;
 pub mod bitwise;
 pub mod diffie_hellman;
+pub mod dsa;
+pub mod ec;
+pub mod ecdsa;
+pub mod gf128;
+pub mod gf128_poly;
+pub mod gf2_matrix;
 pub mod hamming_distance;
 pub mod letter_freq_test;
+pub mod lll;
+pub mod md4;
+pub mod merkle_damgard;
+pub mod prime;
+pub mod rc4;
+pub mod rsa;
 pub mod sha;
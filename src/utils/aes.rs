@@ -1,42 +1,288 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::bitwise::xor_bytes;
+use crate::utils::gf128::{ghash, Gf128};
+use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher};
 use aes::Aes128;
 use block_modes::block_padding::Pkcs7;
-use block_modes::{BlockMode, BlockModeError, Cbc, Ecb};
+use block_modes::{BlockMode, Cbc, Ecb};
 
 type Aes128Cbc = Cbc<Aes128, Pkcs7>;
 type Aes128Ecb = Ecb<Aes128, Pkcs7>;
 
-pub fn aes128_ecb_encrypt(msg: &[u8], key: &[u8]) -> Result<Vec<u8>, BlockModeError> {
-    let cipher = Aes128Ecb::new_from_slices(&key, &[0; 16]).unwrap();
+fn check_key(key: &[u8]) -> Result<()> {
+    if key.len() != 16 {
+        return Err(CryptoError::InvalidKeyLength(key.len()));
+    }
+    Ok(())
+}
+
+fn check_block(block: &[u8]) -> Result<()> {
+    if block.len() != 16 {
+        return Err(CryptoError::InvalidBlockLength(block.len()));
+    }
+    Ok(())
+}
+
+fn new_cipher(key: &[u8]) -> Result<Aes128> {
+    check_key(key)?;
+    Ok(Aes128::new(GenericArray::from_slice(key)))
+}
+
+fn new_cbc(key: &[u8], iv: &[u8]) -> Result<Aes128Cbc> {
+    check_key(key)?;
+    check_block(iv)?;
+    Ok(Aes128Cbc::new_from_slices(key, iv).unwrap())
+}
+
+pub fn aes128_ecb_encrypt(msg: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    check_key(key)?;
+    let cipher = Aes128Ecb::new_from_slices(key, &[0; 16]).unwrap();
     let pos = msg.len();
     let mut buffer = vec![0u8; pos + 16];
     buffer[..pos].copy_from_slice(msg);
-    cipher
-        .encrypt(&mut buffer, msg.len())
-        .and_then(|v| Ok(v.to_vec()))
+    Ok(cipher.encrypt(&mut buffer, msg.len())?.to_vec())
 }
 
-pub fn aes128_ecb_decrypt(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>, BlockModeError> {
-    let cipher = Aes128Ecb::new_from_slices(&key, &[0; 16]).unwrap();
+pub fn aes128_ecb_decrypt(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    check_key(key)?;
+    let cipher = Aes128Ecb::new_from_slices(key, &[0; 16]).unwrap();
     let mut buffer = ciphertext.to_vec();
-    cipher.decrypt(&mut buffer).and_then(|v| Ok(v.to_vec()))
+    Ok(cipher.decrypt(&mut buffer)?.to_vec())
 }
 
-pub fn aes128_cbc_encrypt(msg: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>, BlockModeError> {
-    let cipher = Aes128Cbc::new_from_slices(&key, &iv).unwrap();
+pub fn aes128_cbc_encrypt(msg: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
+    let cipher = new_cbc(key, iv)?;
     let pos = msg.len();
     let mut buffer = vec![0u8; pos + 16];
     buffer[..pos].copy_from_slice(msg);
-    cipher
-        .encrypt(&mut buffer, pos)
-        .and_then(|v| Ok(v.to_vec()))
+    Ok(cipher.encrypt(&mut buffer, pos)?.to_vec())
+}
+
+pub fn aes128_cbc_decrypt(ciphertext: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
+    let cipher = new_cbc(key, iv)?;
+    let mut buffer = ciphertext.to_vec();
+    Ok(cipher.decrypt(&mut buffer)?.to_vec())
+}
+
+// CBC-MAC is the last ciphertext block of (PKCS#7 padded) CBC encryption
+pub fn aes128_cbc_mac(msg: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
+    aes128_cbc_encrypt(msg, key, iv).map(|v| v[v.len() - 16..].to_vec())
+}
+
+// CTR mode with 64 bit little endian nonce followed by 64 bit little
+// endian block counter as the keystream input
+pub fn aes128_ctr(msg: &[u8], key: &[u8], nonce: u64) -> Result<Vec<u8>> {
+    let cipher = new_cipher(key)?;
+    Ok(msg
+        .chunks(16)
+        .enumerate()
+        .flat_map(|(i, chunk)| {
+            let counter_block = [nonce.to_le_bytes(), (i as u64).to_le_bytes()].concat();
+            xor_bytes(chunk, &encrypt_block(&cipher, &counter_block))
+        })
+        .collect())
+}
+
+fn encrypt_block(cipher: &Aes128, block: &[u8]) -> Vec<u8> {
+    let mut block = GenericArray::clone_from_slice(block);
+    cipher.encrypt_block(&mut block);
+    block.to_vec()
+}
+
+// Raw AES-128 of a single 16 byte block
+pub fn aes128_encrypt_block(block: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    check_block(block)?;
+    Ok(encrypt_block(&new_cipher(key)?, block))
+}
+
+pub fn aes128_decrypt_block(block: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    check_block(block)?;
+    let cipher = new_cipher(key)?;
+    let mut block = GenericArray::clone_from_slice(block);
+    cipher.decrypt_block(&mut block);
+    Ok(block.to_vec())
 }
 
-pub fn aes128_cbc_decrypt(
+// Cipher, hash key H & initial counter block J0 (IV || 0^31 || 1 for a 96
+// bit IV, GHASH of the IV otherwise)
+fn gcm_setup(key: &[u8], iv: &[u8]) -> Result<(Aes128, Gf128, u128)> {
+    let cipher = new_cipher(key)?;
+    if iv.is_empty() {
+        return Err(CryptoError::InvalidBlockLength(0));
+    }
+    let h = Gf128::from_block(&encrypt_block(&cipher, &[0; 16]));
+    let j0 = if iv.len() == 12 {
+        let mut block = [0_u8; 16];
+        block[..12].copy_from_slice(iv);
+        block[15] = 1;
+        u128::from_be_bytes(block)
+    } else {
+        ghash(h, &[], iv).0
+    };
+    Ok((cipher, h, j0))
+}
+
+// CTR part of GCM, starting from inc32(J0) & incrementing the low 32 bits
+fn gcm_ctr(msg: &[u8], cipher: &Aes128, j0: u128) -> Vec<u8> {
+    msg.chunks(16)
+        .enumerate()
+        .flat_map(|(i, chunk)| {
+            let counter = (j0 as u32).wrapping_add(i as u32 + 1);
+            let block = (j0 & !(u32::MAX as u128)) | counter as u128;
+            xor_bytes(chunk, &encrypt_block(cipher, &block.to_be_bytes()))
+        })
+        .collect()
+}
+
+fn gcm_tag(cipher: &Aes128, h: Gf128, j0: u128, aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let s = ghash(h, aad, ciphertext).to_block();
+    xor_bytes(&s, &encrypt_block(cipher, &j0.to_be_bytes()))
+}
+
+// Tag of ciphertext & aad, without decrypting
+pub fn aes128_gcm_tag(ciphertext: &[u8], key: &[u8], iv: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let (cipher, h, j0) = gcm_setup(key, iv)?;
+    Ok(gcm_tag(&cipher, h, j0, aad, ciphertext))
+}
+
+// AES-128-GCM, returning (ciphertext, 16 byte tag)
+pub fn aes128_gcm_encrypt(
+    msg: &[u8],
+    key: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let (cipher, h, j0) = gcm_setup(key, iv)?;
+    let ciphertext = gcm_ctr(msg, &cipher, j0);
+    let tag = gcm_tag(&cipher, h, j0, aad, &ciphertext);
+    Ok((ciphertext, tag))
+}
+
+// Plaintext, if tag authenticates ciphertext & aad
+pub fn aes128_gcm_decrypt(
     ciphertext: &[u8],
     key: &[u8],
     iv: &[u8],
-) -> Result<Vec<u8>, BlockModeError> {
-    let cipher = Aes128Cbc::new_from_slices(&key, &iv).unwrap();
-    let mut buffer = ciphertext.to_vec();
-    cipher.decrypt(&mut buffer).and_then(|v| Ok(v.to_vec()))
+    aad: &[u8],
+    tag: &[u8],
+) -> Result<Vec<u8>> {
+    let (cipher, h, j0) = gcm_setup(key, iv)?;
+    if gcm_tag(&cipher, h, j0, aad, ciphertext) != tag {
+        return Err(CryptoError::InvalidTag);
+    }
+    Ok(gcm_ctr(ciphertext, &cipher, j0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // NIST GCM spec test cases as (key, iv, plaintext, aad, ciphertext, tag)
+    const GCM_VECTORS: [(&str, &str, &str, &str, &str, &str); 5] = [
+        (
+            "00000000000000000000000000000000",
+            "000000000000000000000000",
+            "",
+            "",
+            "",
+            "58e2fccefa7e3061367f1d57a4e7455a",
+        ),
+        (
+            "00000000000000000000000000000000",
+            "000000000000000000000000",
+            "00000000000000000000000000000000",
+            "",
+            "0388dace60b6a392f328c2b971b2fe78",
+            "ab6e47d42cec13bdf53a67b21257bddf",
+        ),
+        (
+            "feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255",
+            "",
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985",
+            "4d5c2af327cd64a62cf35abd2ba6fab4",
+        ),
+        (
+            "feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+            "5bc94fbc3221a5db94fae95ae7121a47",
+        ),
+        (
+            "feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbad",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f83766e5f97b6c7423\
+             73806900e49f24b22b097544d4896b424989b5e1ebac0f07c23f4598",
+            "3612d2e79e3b0785561be14aaca2fccb",
+        ),
+    ];
+
+    #[test]
+    fn test_aes128_gcm() {
+        for (key, iv, msg, aad, ciphertext, tag) in GCM_VECTORS.iter() {
+            let [key, iv, msg, aad, ciphertext, tag] =
+                [key, iv, msg, aad, ciphertext, tag].map(|s| hex::decode(s).unwrap());
+            assert_eq!(
+                aes128_gcm_encrypt(&msg, &key, &iv, &aad),
+                Ok((ciphertext.clone(), tag.clone()))
+            );
+            assert_eq!(
+                aes128_gcm_decrypt(&ciphertext, &key, &iv, &aad, &tag),
+                Ok(msg)
+            );
+
+            let mut forged = tag.clone();
+            forged[0] ^= 1;
+            assert_eq!(
+                aes128_gcm_decrypt(&ciphertext, &key, &iv, &aad, &forged),
+                Err(CryptoError::InvalidTag)
+            );
+            assert_eq!(
+                aes128_gcm_decrypt(&ciphertext, &key, &iv, b"x", &tag),
+                Err(CryptoError::InvalidTag)
+            );
+        }
+    }
+
+    #[test]
+    fn test_aes128_errors() {
+        let key = b"YELLOW SUBMARINE";
+        assert_eq!(
+            aes128_ecb_encrypt(b"msg", b"short"),
+            Err(CryptoError::InvalidKeyLength(5))
+        );
+        assert_eq!(
+            aes128_cbc_encrypt(b"msg", key, b"iv"),
+            Err(CryptoError::InvalidBlockLength(2))
+        );
+        assert_eq!(
+            aes128_encrypt_block(b"not a block", key),
+            Err(CryptoError::InvalidBlockLength(11))
+        );
+        assert_eq!(
+            aes128_ctr(b"msg", &[0; 32], 0),
+            Err(CryptoError::InvalidKeyLength(32))
+        );
+
+        // Truncated ciphertext & corrupted padding
+        let ciphertext = aes128_cbc_encrypt(b"msg", key, &[0; 16]).unwrap();
+        assert_eq!(
+            aes128_cbc_decrypt(&ciphertext[..10], key, &[0; 16]),
+            Err(CryptoError::BadPadding)
+        );
+        assert_eq!(
+            aes128_ecb_decrypt(&aes128_encrypt_block(&[0; 16], key).unwrap(), key),
+            Err(CryptoError::BadPadding)
+        );
+    }
 }
@@ -1,5 +1,4 @@
 use num_bigint::BigUint;
-use num_traits::{One, Zero};
 
 pub struct DH {
     pub p: Vec<u8>,
@@ -27,14 +26,5 @@ fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
     let exp = BigUint::from_bytes_be(exponent);
     let b = BigUint::from_bytes_be(base);
     let m = BigUint::from_bytes_be(modulus);
-
-    let mut e: BigUint = Zero::zero();
-    let mut c: BigUint = One::one();
-
-    while e < exp {
-        e = e + 1_u8;
-
-        c = (b.clone() * c) % m.clone();
-    }
-    c.to_bytes_be()
+    b.modpow(&exp, &m).to_bytes_be()
 }
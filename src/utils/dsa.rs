@@ -0,0 +1,74 @@
+use crate::utils::sha::sha1_hash;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+pub struct DSA {
+    pub p: BigUint,
+    pub q: BigUint,
+    pub g: BigUint,
+}
+
+impl DSA {
+    pub fn new(p: &BigUint, q: &BigUint, g: &BigUint) -> Self {
+        Self {
+            p: p.clone(),
+            q: q.clone(),
+            g: g.clone(),
+        }
+    }
+
+    // Returns (private key, public key) pair
+    pub fn gen_key_pair(&self) -> (BigUint, BigUint) {
+        let x = rand::thread_rng().gen_biguint_range(&One::one(), &self.q);
+        let y = self.g.modpow(&x, &self.p);
+        (x, y)
+    }
+
+    pub fn sign(&self, msg: &[u8], x: &BigUint) -> Signature {
+        loop {
+            let k = rand::thread_rng().gen_biguint_range(&One::one(), &self.q);
+            if let Some(sig) = self.sign_with_k(msg, x, &k) {
+                if !sig.r.is_zero() && !sig.s.is_zero() {
+                    return sig;
+                }
+            }
+        }
+    }
+
+    // Signs using the given nonce k, without rejecting degenerate r or s
+    pub fn sign_with_k(&self, msg: &[u8], x: &BigUint, k: &BigUint) -> Option<Signature> {
+        let h = hash_msg(msg);
+        let r = self.g.modpow(k, &self.p) % &self.q;
+        let k_inv = k.modinv(&self.q)?;
+        let s = (k_inv * (h + x * &r)) % &self.q;
+        Some(Signature { r, s })
+    }
+
+    pub fn verify(&self, msg: &[u8], sig: &Signature, y: &BigUint) -> bool {
+        if sig.r.is_zero() || sig.r >= self.q || sig.s.is_zero() || sig.s >= self.q {
+            return false;
+        }
+        self.verify_unchecked(msg, sig, y)
+    }
+
+    // Verifies skipping the 0 < r < q and 0 < s < q range checks
+    pub fn verify_unchecked(&self, msg: &[u8], sig: &Signature, y: &BigUint) -> bool {
+        let w = match sig.s.modinv(&self.q) {
+            Some(w) => w,
+            None => return false,
+        };
+        let u1 = (hash_msg(msg) * &w) % &self.q;
+        let u2 = (&sig.r * &w) % &self.q;
+        let v = ((self.g.modpow(&u1, &self.p) * y.modpow(&u2, &self.p)) % &self.p) % &self.q;
+        v == sig.r
+    }
+}
+
+pub fn hash_msg(msg: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&sha1_hash(msg))
+}
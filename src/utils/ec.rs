@@ -0,0 +1,350 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+// Cryptopals #59 curve y^2 = x^3 - 95051x + 11279326 over GF(p), with base
+// point of prime order q
+const P_59: &str = "233970423115425145524320034830162017933";
+const A_59: &str = "233970423115425145524320034830161922882"; // -95051 mod p
+const B_59: u32 = 11279326;
+const GX_59: u32 = 182;
+const GY_59: &str = "85518893674295321206118380980485522083";
+const Q_59: &str = "29246302889428143187362802287225875743";
+const ORDER_59: &str = "233970423115425145498902418297807005944";
+
+// Same curve in Montgomery form v^2 = u^3 + 534u^2 + u, where u = x - 178
+const A_60: u32 = 534;
+const U_60: u32 = 4;
+
+fn from_dec(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 10).unwrap()
+}
+
+// (a - b) mod p for a, b < p
+pub fn sub_mod(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint {
+    (a + p - b) % p
+}
+
+pub fn inv_mod(a: &BigUint, p: &BigUint) -> BigUint {
+    a.modpow(&(p - 2_u8), p)
+}
+
+pub fn is_square_mod(n: &BigUint, p: &BigUint) -> bool {
+    let n = n % p;
+    n.is_zero() || n.modpow(&((p - 1_u8) >> 1), p).is_one()
+}
+
+// Square root of n mod odd prime p (Tonelli-Shanks), if n is a square
+pub fn sqrt_mod(n: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let n = n % p;
+    if n.is_zero() {
+        return Some(n);
+    }
+    if !is_square_mod(&n, p) {
+        return None;
+    }
+
+    // p - 1 = q * 2^s with q odd, and z any non square
+    let s = (p - 1_u8).trailing_zeros().unwrap();
+    let q = (p - 1_u8) >> s;
+    let mut z = BigUint::from(2_u8);
+    while is_square_mod(&z, p) {
+        z += 1_u8;
+    }
+
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = n.modpow(&q, p);
+    let mut r = n.modpow(&((&q + 1_u8) >> 1), p);
+    while !t.is_one() {
+        // Least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut t_pow = t.clone();
+        while !t_pow.is_one() {
+            t_pow = (&t_pow * &t_pow) % p;
+            i += 1;
+        }
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), p);
+        m = i;
+        c = (&b * &b) % p;
+        t = (t * &c) % p;
+        r = (r * b) % p;
+    }
+    Some(r)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Point {
+    Infinity,
+    Affine(BigUint, BigUint),
+}
+
+// Curve y^2 = x^3 + ax + b over GF(p)
+#[derive(Clone, Debug)]
+pub struct WeierstrassCurve {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub p: BigUint,
+}
+
+impl WeierstrassCurve {
+    pub fn new(a: &BigUint, b: &BigUint, p: &BigUint) -> Self {
+        Self {
+            a: a % p,
+            b: b % p,
+            p: p.clone(),
+        }
+    }
+
+    // x^3 + ax + b
+    pub fn rhs(&self, x: &BigUint) -> BigUint {
+        (x * x * x + &self.a * x + &self.b) % &self.p
+    }
+
+    pub fn is_on_curve(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => x < &self.p && y < &self.p && (y * y) % &self.p == self.rhs(x),
+        }
+    }
+
+    pub fn neg(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::Affine(x.clone(), sub_mod(&BigUint::zero(), y, &self.p)),
+        }
+    }
+
+    pub fn add(&self, p1: &Point, p2: &Point) -> Point {
+        let p = &self.p;
+        let (x1, y1, x2, y2) = match (p1, p2) {
+            (Point::Infinity, _) => return p2.clone(),
+            (_, Point::Infinity) => return p1.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        if *p1 == self.neg(p2) {
+            return Point::Infinity;
+        }
+
+        let m = if p1 == p2 {
+            // Tangent slope (3x^2 + a) / 2y
+            ((BigUint::from(3_u8) * x1 * x1 + &self.a) * inv_mod(&(y1 * 2_u8), p)) % p
+        } else {
+            (sub_mod(y2, y1, p) * inv_mod(&sub_mod(x2, x1, p), p)) % p
+        };
+        let x3 = sub_mod(&sub_mod(&(&m * &m % p), x1, p), x2, p);
+        let y3 = sub_mod(&(m * sub_mod(x1, &x3, p) % p), y1, p);
+        Point::Affine(x3, y3)
+    }
+
+    // Double & add
+    pub fn scalar_mul(&self, point: &Point, k: &BigUint) -> Point {
+        let mut result = Point::Infinity;
+        for i in (0..k.bits()).rev() {
+            result = self.add(&result, &result);
+            if k.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    // Random point (other than infinity) on the curve
+    pub fn random_point(&self) -> Point {
+        loop {
+            let x = rand::thread_rng().gen_biguint_below(&self.p);
+            if let Some(y) = sqrt_mod(&self.rhs(&x), &self.p) {
+                return Point::Affine(x, y);
+            }
+        }
+    }
+}
+
+// Curve Bv^2 = u^3 + Au^2 + u over GF(p), used through u coordinates only
+#[derive(Clone, Debug)]
+pub struct MontgomeryCurve {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub p: BigUint,
+}
+
+impl MontgomeryCurve {
+    pub fn new(a: &BigUint, b: &BigUint, p: &BigUint) -> Self {
+        Self {
+            a: a % p,
+            b: b % p,
+            p: p.clone(),
+        }
+    }
+
+    // Whether some v on the curve (rather than its twist) goes with u
+    pub fn is_on_curve(&self, u: &BigUint) -> bool {
+        let p = &self.p;
+        let rhs = (u * u * u + &self.a * u * u + u) % p;
+        u < p && is_square_mod(&(rhs * inv_mod(&self.b, p)), p)
+    }
+
+    // Montgomery ladder giving u coordinate of k * (point with coordinate
+    // u), with zero for the point at infinity
+    pub fn ladder(&self, u: &BigUint, k: &BigUint) -> BigUint {
+        let p = &self.p;
+        let (mut u2, mut w2) = (BigUint::one(), BigUint::zero());
+        let (mut u3, mut w3) = (u.clone(), BigUint::one());
+        for i in (0..k.bits().max(p.bits())).rev() {
+            let bit = k.bit(i);
+            if bit {
+                std::mem::swap(&mut u2, &mut u3);
+                std::mem::swap(&mut w2, &mut w3);
+            }
+
+            let t1 = sub_mod(&(&u2 * &u3 % p), &(&w2 * &w3 % p), p);
+            let t2 = sub_mod(&(&u2 * &w3 % p), &(&w2 * &u3 % p), p);
+            let new_u3 = &t1 * &t1 % p;
+            let new_w3 = u * (&t2 * &t2 % p) % p;
+
+            let u2_sq = &u2 * &u2 % p;
+            let w2_sq = &w2 * &w2 % p;
+            let t3 = sub_mod(&u2_sq, &w2_sq, p);
+            let new_u2 = &t3 * &t3 % p;
+            let new_w2 = (&u2 * &w2 * 4_u8 % p) * ((u2_sq + &self.a * &u2 * &w2 + w2_sq) % p) % p;
+
+            (u2, w2, u3, w3) = (new_u2, new_w2, new_u3, new_w3);
+            if bit {
+                std::mem::swap(&mut u2, &mut u3);
+                std::mem::swap(&mut w2, &mut w3);
+            }
+        }
+        u2 * w2.modpow(&(p - 2_u8), p) % p
+    }
+}
+
+pub struct ECDH {
+    pub curve: WeierstrassCurve,
+    pub g: Point,
+    pub q: BigUint,
+}
+
+impl ECDH {
+    pub fn new(curve: &WeierstrassCurve, g: &Point, q: &BigUint) -> Self {
+        Self {
+            curve: curve.clone(),
+            g: g.clone(),
+            q: q.clone(),
+        }
+    }
+
+    // Cryptopals #59 parameters
+    pub fn challenge_59() -> Self {
+        let curve = WeierstrassCurve::new(&from_dec(A_59), &B_59.into(), &from_dec(P_59));
+        let g = Point::Affine(GX_59.into(), from_dec(GY_59));
+        Self::new(&curve, &g, &from_dec(Q_59))
+    }
+
+    // Order of the whole group of points of the #59 curve
+    pub fn curve_order_59() -> BigUint {
+        from_dec(ORDER_59)
+    }
+
+    // Returns (private key, public key) pair
+    pub fn gen_key_pair(&self) -> (BigUint, Point) {
+        let x = rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.q);
+        let pub_key = self.curve.scalar_mul(&self.g, &x);
+        (x, pub_key)
+    }
+
+    pub fn gen_session_key(&self, pub_key: &Point, x: &BigUint) -> Point {
+        self.curve.scalar_mul(pub_key, x)
+    }
+
+    // Public key is a point of the curve in the subgroup generated by g
+    pub fn is_valid_pub_key(&self, pub_key: &Point) -> bool {
+        *pub_key != Point::Infinity
+            && self.curve.is_on_curve(pub_key)
+            && self.curve.scalar_mul(pub_key, &self.q) == Point::Infinity
+    }
+}
+
+pub struct MontgomeryECDH {
+    pub curve: MontgomeryCurve,
+    pub u: BigUint,
+    pub q: BigUint,
+}
+
+impl MontgomeryECDH {
+    pub fn new(curve: &MontgomeryCurve, u: &BigUint, q: &BigUint) -> Self {
+        Self {
+            curve: curve.clone(),
+            u: u.clone(),
+            q: q.clone(),
+        }
+    }
+
+    // Cryptopals #60 parameters (#59 curve in Montgomery form)
+    pub fn challenge_60() -> Self {
+        let curve = MontgomeryCurve::new(&A_60.into(), &BigUint::one(), &from_dec(P_59));
+        Self::new(&curve, &U_60.into(), &from_dec(Q_59))
+    }
+
+    // Returns (private key, public key) pair
+    pub fn gen_key_pair(&self) -> (BigUint, BigUint) {
+        let x = rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.q);
+        let pub_key = self.curve.ladder(&self.u, &x);
+        (x, pub_key)
+    }
+
+    pub fn gen_session_key(&self, pub_key: &BigUint, x: &BigUint) -> BigUint {
+        self.curve.ladder(pub_key, x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ec_weierstrass() {
+        let ecdh = ECDH::challenge_59();
+        let curve = &ecdh.curve;
+        assert!(curve.is_on_curve(&ecdh.g));
+        assert_eq!(curve.scalar_mul(&ecdh.g, &ecdh.q), Point::Infinity);
+        let point = curve.random_point();
+        assert!(curve.is_on_curve(&point));
+        assert_eq!(
+            curve.scalar_mul(&point, &ECDH::curve_order_59()),
+            Point::Infinity
+        );
+
+        let (a, pub_a) = ecdh.gen_key_pair();
+        let (b, pub_b) = ecdh.gen_key_pair();
+        assert!(ecdh.is_valid_pub_key(&pub_a));
+        assert_eq!(
+            ecdh.gen_session_key(&pub_b, &a),
+            ecdh.gen_session_key(&pub_a, &b)
+        );
+
+        let off_curve = Point::Affine(GX_59.into(), GY_59.parse::<BigUint>().unwrap() + 1_u8);
+        assert!(!curve.is_on_curve(&off_curve));
+        assert!(!ecdh.is_valid_pub_key(&off_curve));
+    }
+
+    #[test]
+    fn test_ec_montgomery() {
+        let ecdh = MontgomeryECDH::challenge_60();
+        assert!(ecdh.curve.is_on_curve(&ecdh.u));
+        assert!(ecdh.curve.ladder(&ecdh.u, &ecdh.q).is_zero());
+
+        let (a, pub_a) = ecdh.gen_key_pair();
+        let (b, pub_b) = ecdh.gen_key_pair();
+        assert_eq!(
+            ecdh.gen_session_key(&pub_b, &a),
+            ecdh.gen_session_key(&pub_a, &b)
+        );
+
+        // Agrees with Weierstrass form under u = x - 178
+        let weierstrass = ECDH::challenge_59();
+        let p = &weierstrass.curve.p;
+        match weierstrass.curve.scalar_mul(&weierstrass.g, &a) {
+            Point::Affine(x, _) => assert_eq!(pub_a, sub_mod(&x, &178_u32.into(), p)),
+            Point::Infinity => panic!("Unexpected point at infinity!"),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::utils::dsa::{hash_msg, Signature};
+use crate::utils::ec::{Point, WeierstrassCurve, ECDH};
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+pub struct ECDSA {
+    pub curve: WeierstrassCurve,
+    pub g: Point,
+    pub q: BigUint,
+}
+
+impl ECDSA {
+    pub fn new(curve: &WeierstrassCurve, g: &Point, q: &BigUint) -> Self {
+        Self {
+            curve: curve.clone(),
+            g: g.clone(),
+            q: q.clone(),
+        }
+    }
+
+    // Cryptopals #59 curve & base point
+    pub fn challenge_59() -> Self {
+        let ecdh = ECDH::challenge_59();
+        Self::new(&ecdh.curve, &ecdh.g, &ecdh.q)
+    }
+
+    // Returns (private key, public key) pair
+    pub fn gen_key_pair(&self) -> (BigUint, Point) {
+        let d = rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.q);
+        let pub_key = self.curve.scalar_mul(&self.g, &d);
+        (d, pub_key)
+    }
+
+    // SHA-1 of msg, truncated to bit length of q
+    pub fn hash(&self, msg: &[u8]) -> BigUint {
+        let h = hash_msg(msg);
+        let excess = 160_u64.saturating_sub(self.q.bits());
+        h >> excess
+    }
+
+    pub fn sign(&self, msg: &[u8], d: &BigUint) -> Signature {
+        loop {
+            let k = rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.q);
+            if let Some(sig) = self.sign_with_k(msg, d, &k) {
+                if !sig.r.is_zero() && !sig.s.is_zero() {
+                    return sig;
+                }
+            }
+        }
+    }
+
+    // Signs using the given nonce k, without rejecting degenerate r or s
+    pub fn sign_with_k(&self, msg: &[u8], d: &BigUint, k: &BigUint) -> Option<Signature> {
+        let r = match self.curve.scalar_mul(&self.g, k) {
+            Point::Affine(x, _) => x % &self.q,
+            Point::Infinity => return None,
+        };
+        let k_inv = k.modinv(&self.q)?;
+        let s = (k_inv * (self.hash(msg) + d * &r)) % &self.q;
+        Some(Signature { r, s })
+    }
+
+    pub fn verify(&self, msg: &[u8], sig: &Signature, pub_key: &Point) -> bool {
+        if sig.r.is_zero() || sig.r >= self.q || sig.s.is_zero() || sig.s >= self.q {
+            return false;
+        }
+        let w = match sig.s.modinv(&self.q) {
+            Some(w) => w,
+            None => return false,
+        };
+        let u1 = (self.hash(msg) * &w) % &self.q;
+        let u2 = (&sig.r * &w) % &self.q;
+        let point = self.curve.add(
+            &self.curve.scalar_mul(&self.g, &u1),
+            &self.curve.scalar_mul(pub_key, &u2),
+        );
+        match point {
+            Point::Affine(x, _) => x % &self.q == sig.r,
+            Point::Infinity => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ECDSA;
+
+    #[test]
+    fn test_ecdsa() {
+        let ecdsa = ECDSA::challenge_59();
+        let (d, pub_key) = ecdsa.gen_key_pair();
+        let sig = ecdsa.sign(b"hi mom", &d);
+        assert!(ecdsa.verify(b"hi mom", &sig, &pub_key));
+        assert!(!ecdsa.verify(b"hi dad", &sig, &pub_key));
+
+        let (_, other_key) = ecdsa.gen_key_pair();
+        assert!(!ecdsa.verify(b"hi mom", &sig, &other_key));
+    }
+}
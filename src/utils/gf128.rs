@@ -0,0 +1,179 @@
+use crate::utils::gf2_matrix::BitMatrix;
+use std::ops::{Add, Mul};
+
+// x^128 + x^7 + x^2 + x + 1, in GCM bit order (bit 127 is x^0)
+const R: u128 = 0xe1 << 120;
+
+// Element of GF(2^128) with GCM bit order: first bit of a 16 byte block
+// (most significant bit of the u128) is the coefficient of x^0
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Gf128(pub u128);
+
+impl Gf128 {
+    pub fn zero() -> Self {
+        Self(0)
+    }
+
+    pub fn one() -> Self {
+        Self(1 << 127)
+    }
+
+    pub fn from_block(block: &[u8]) -> Self {
+        let mut bytes = [0_u8; 16];
+        bytes[..block.len()].copy_from_slice(block);
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    pub fn to_block(self) -> Vec<u8> {
+        self.0.to_be_bytes().to_vec()
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn pow(self, mut e: u128) -> Self {
+        let (mut result, mut base) = (Self::one(), self);
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        result
+    }
+
+    // x^i
+    pub fn x_pow(i: usize) -> Self {
+        Self(1 << (127 - i))
+    }
+
+    // Coefficient of x^i
+    pub fn bit(self, i: usize) -> bool {
+        (self.0 >> (127 - i)) & 1 == 1
+    }
+
+    // Element as a column vector of its coefficients, x^0 first
+    pub fn to_column(self) -> BitMatrix {
+        let mut column = BitMatrix::zero(128, 1);
+        for i in 0..128 {
+            column.set(i, 0, self.bit(i));
+        }
+        column
+    }
+
+    // Element from column c of a matrix with 128 rows
+    pub fn from_column(m: &BitMatrix, c: usize) -> Self {
+        (0..128)
+            .filter(|&i| m.get(i, c))
+            .fold(Self::zero(), |acc, i| acc + Self::x_pow(i))
+    }
+
+    // Matrix of y -> self * y: column i is self * x^i
+    pub fn mul_matrix(self) -> BitMatrix {
+        Self::linear_map(|y| self * y)
+    }
+
+    // Matrix of y -> y^2, which is linear in characteristic 2
+    pub fn square_matrix() -> BitMatrix {
+        Self::linear_map(|y| y * y)
+    }
+
+    fn linear_map(f: impl Fn(Self) -> Self) -> BitMatrix {
+        let mut m = BitMatrix::zero(128, 128);
+        for i in 0..128 {
+            let column = f(Self::x_pow(i));
+            for j in 0..128 {
+                m.set(j, i, column.bit(j));
+            }
+        }
+        m
+    }
+
+    // Multiplicative group has order 2^128 - 1
+    pub fn inv(self) -> Self {
+        if self.is_zero() {
+            panic!("Zero has no inverse!");
+        }
+        self.pow(u128::MAX - 1)
+    }
+}
+
+impl Add for Gf128 {
+    type Output = Self;
+
+    // Coefficients are in GF(2), so addition is xor
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+}
+
+impl Mul for Gf128 {
+    type Output = Self;
+
+    // Shift & add, reducing by R whenever x^127 overflows
+    fn mul(self, other: Self) -> Self {
+        let (mut z, mut v) = (0, other.0);
+        for i in (0..128).rev() {
+            if (self.0 >> i) & 1 == 1 {
+                z ^= v;
+            }
+            v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+        }
+        Self(z)
+    }
+}
+
+// GHASH input blocks: zero padded aad & ciphertext, then their bit lengths
+pub fn ghash_blocks(aad: &[u8], ciphertext: &[u8]) -> Vec<Gf128> {
+    let lengths = [
+        (aad.len() as u64 * 8).to_be_bytes(),
+        (ciphertext.len() as u64 * 8).to_be_bytes(),
+    ]
+    .concat();
+    aad.chunks(16)
+        .chain(ciphertext.chunks(16))
+        .chain(std::iter::once(&lengths[..]))
+        .map(Gf128::from_block)
+        .collect()
+}
+
+// GHASH of aad & ciphertext under hash key h
+pub fn ghash(h: Gf128, aad: &[u8], ciphertext: &[u8]) -> Gf128 {
+    ghash_blocks(aad, ciphertext)
+        .into_iter()
+        .fold(Gf128::zero(), |acc, block| (acc + block) * h)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gf128() {
+        let a = Gf128(0x66e94bd4ef8a2c3b884cfa59ca342b2e);
+        let b = Gf128(0x0388dace60b6a392f328c2b971b2fe78);
+        assert_eq!(a * Gf128::one(), a);
+        assert_eq!(a * b, b * a);
+        assert_eq!(a * a.inv(), Gf128::one());
+        assert_eq!(a * (a + b), a * a + a * b);
+        // x * x^127 = x^128 = x^7 + x^2 + x + 1
+        assert_eq!(Gf128(1 << 126) * Gf128(1), Gf128(R));
+
+        // Linear maps agree with field arithmetic
+        let sq = Gf128::square_matrix();
+        assert_eq!(
+            Gf128::from_column(&a.mul_matrix().mul(&b.to_column()), 0),
+            a * b
+        );
+        assert_eq!(Gf128::from_column(&sq.mul(&b.to_column()), 0), b * b);
+        let fourth = Gf128::from_column(&sq.mul(&sq).mul(&b.to_column()), 0);
+        assert_eq!(fourth, b.pow(4));
+
+        // GHASH of NIST GCM test case 2
+        let tag = ghash(a, &[], &b.to_block());
+        assert_eq!(tag, Gf128(0xf38cbb1ad69223dcc3457ae5b6b0f885));
+    }
+}
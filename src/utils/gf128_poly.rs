@@ -0,0 +1,181 @@
+use crate::utils::gf128::Gf128;
+use num_bigint::BigUint;
+
+// Polynomial over GF(2^128), coefficients from x^0 up, without trailing
+// zeros (so zero polynomial has none)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poly(Vec<Gf128>);
+
+impl Poly {
+    pub fn new(coeffs: &[Gf128]) -> Self {
+        let len = coeffs.len() - coeffs.iter().rev().take_while(|c| c.is_zero()).count();
+        Self(coeffs[..len].to_vec())
+    }
+
+    pub fn zero() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn one() -> Self {
+        Self(vec![Gf128::one()])
+    }
+
+    // x
+    pub fn x() -> Self {
+        Self(vec![Gf128::zero(), Gf128::one()])
+    }
+
+    pub fn coeffs(&self) -> &[Gf128] {
+        &self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.0.len().checked_sub(1)
+    }
+
+    pub fn lead(&self) -> Gf128 {
+        *self.0.last().unwrap_or(&Gf128::zero())
+    }
+
+    pub fn eval(&self, x: Gf128) -> Gf128 {
+        self.0
+            .iter()
+            .rev()
+            .fold(Gf128::zero(), |acc, &c| acc * x + c)
+    }
+
+    // Subtraction is the same in characteristic 2
+    pub fn add(&self, other: &Poly) -> Poly {
+        let len = self.0.len().max(other.0.len());
+        let coeffs: Vec<Gf128> = (0..len)
+            .map(|i| {
+                let a = self.0.get(i).copied().unwrap_or_else(Gf128::zero);
+                let b = other.0.get(i).copied().unwrap_or_else(Gf128::zero);
+                a + b
+            })
+            .collect();
+        Poly::new(&coeffs)
+    }
+
+    pub fn mul(&self, other: &Poly) -> Poly {
+        if self.is_zero() || other.is_zero() {
+            return Poly::zero();
+        }
+        let mut coeffs = vec![Gf128::zero(); self.0.len() + other.0.len() - 1];
+        for (i, &a) in self.0.iter().enumerate() {
+            for (j, &b) in other.0.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j] + a * b;
+            }
+        }
+        Poly::new(&coeffs)
+    }
+
+    pub fn scale(&self, c: Gf128) -> Poly {
+        Poly::new(&self.0.iter().map(|&a| a * c).collect::<Vec<_>>())
+    }
+
+    // Same polynomial divided by its leading coefficient
+    pub fn monic(&self) -> Poly {
+        if self.is_zero() {
+            return Poly::zero();
+        }
+        self.scale(self.lead().inv())
+    }
+
+    // (quotient, remainder) of long division by a non-zero polynomial
+    pub fn divmod(&self, divisor: &Poly) -> (Poly, Poly) {
+        let d = divisor.degree().expect("Division by zero polynomial!");
+        let lead_inv = divisor.lead().inv();
+        let mut rem = self.0.clone();
+        let mut quot = vec![Gf128::zero(); self.0.len().saturating_sub(d)];
+        for i in (d..self.0.len()).rev() {
+            let c = rem[i] * lead_inv;
+            quot[i - d] = c;
+            for (r, &b) in rem[i - d..=i].iter_mut().zip(&divisor.0) {
+                *r = *r + c * b;
+            }
+        }
+        (Poly::new(&quot), Poly::new(&rem))
+    }
+
+    pub fn rem(&self, divisor: &Poly) -> Poly {
+        self.divmod(divisor).1
+    }
+
+    // Monic greatest common divisor
+    pub fn gcd(&self, other: &Poly) -> Poly {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    // self^e mod m, by square & multiply
+    pub fn modexp(&self, e: &BigUint, m: &Poly) -> Poly {
+        let base = self.rem(m);
+        (0..e.bits()).rev().fold(Poly::one().rem(m), |acc, i| {
+            let acc = acc.mul(&acc).rem(m);
+            if e.bit(i) {
+                acc.mul(&base).rem(m)
+            } else {
+                acc
+            }
+        })
+    }
+
+    // Formal derivative: i * c_i vanishes for even i
+    pub fn derivative(&self) -> Poly {
+        let coeffs: Vec<Gf128> = self
+            .0
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| if i % 2 == 1 { c } else { Gf128::zero() })
+            .collect();
+        Poly::new(&coeffs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gf128_poly() {
+        let (a, b, c) = (Gf128(3), Gf128(0xdeadbeef << 64), Gf128(1 << 100));
+        let f = Poly::new(&[a, b, Gf128::one()]);
+        let g = Poly::new(&[c, Gf128::one()]);
+        let h = Poly::new(&[b, a, c, Gf128::zero()]);
+        assert_eq!(h.degree(), Some(2));
+
+        let fg = f.mul(&g);
+        assert_eq!(fg.degree(), Some(3));
+        assert_eq!(fg.eval(b), f.eval(b) * g.eval(b));
+        assert_eq!(fg.divmod(&g), (f.clone(), Poly::zero()));
+        let (q, r) = fg.add(&Poly::one()).divmod(&f);
+        assert_eq!(q, g);
+        assert_eq!(r, Poly::one());
+        assert_eq!(fg.gcd(&g.mul(&h)), g);
+        assert_eq!(f.add(&f), Poly::zero());
+
+        // Derivative of (x + c)^2 vanishes, of f is b
+        assert_eq!(g.mul(&g).derivative(), Poly::zero());
+        assert_eq!(f.derivative(), Poly::new(&[b]));
+
+        // x^(2^128) = x in GF(2^128)[x] / (x - c) since c^(2^128) = c
+        let e = BigUint::from(1_u8) << 128;
+        assert_eq!(Poly::x().modexp(&e, &g), Poly::new(&[c]));
+        assert_eq!(
+            f.modexp(&BigUint::from(3_u8), &h),
+            f.mul(&f).mul(&f).rem(&h)
+        );
+    }
+}
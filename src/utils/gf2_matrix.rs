@@ -0,0 +1,203 @@
+use rand::Rng;
+
+// Matrix over GF(2), each row packed into 64 bit words
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Vec<u64>>,
+}
+
+impl BitMatrix {
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![vec![0; cols.div_ceil(64)]; rows],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zero(n, n);
+        for i in 0..n {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> bool {
+        (self.data[r][c / 64] >> (c % 64)) & 1 == 1
+    }
+
+    pub fn set(&mut self, r: usize, c: usize, bit: bool) {
+        if bit {
+            self.data[r][c / 64] |= 1 << (c % 64);
+        } else {
+            self.data[r][c / 64] &= !(1 << (c % 64));
+        }
+    }
+
+    pub fn row(&self, r: usize) -> Vec<bool> {
+        (0..self.cols).map(|c| self.get(r, c)).collect()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.data.iter().flatten().all(|&w| w == 0)
+    }
+
+    // Adds row src into row dst
+    fn add_row(&mut self, dst: usize, src: usize) {
+        let src = self.data[src].clone();
+        for (a, b) in self.data[dst].iter_mut().zip(src) {
+            *a ^= b;
+        }
+    }
+
+    pub fn add(&self, other: &BitMatrix) -> BitMatrix {
+        if (self.rows, self.cols) != (other.rows, other.cols) {
+            panic!("Matrix dimensions don't match!");
+        }
+        let mut sum = self.clone();
+        for (a, b) in sum
+            .data
+            .iter_mut()
+            .flatten()
+            .zip(other.data.iter().flatten())
+        {
+            *a ^= b;
+        }
+        sum
+    }
+
+    // Row r of the product is the sum of the rows of other picked by row r
+    // of self
+    pub fn mul(&self, other: &BitMatrix) -> BitMatrix {
+        if self.cols != other.rows {
+            panic!("Matrix dimensions don't match!");
+        }
+        let mut product = BitMatrix::zero(self.rows, other.cols);
+        for r in 0..self.rows {
+            for k in (0..self.cols).filter(|&k| self.get(r, k)) {
+                for (a, b) in product.data[r].iter_mut().zip(&other.data[k]) {
+                    *a ^= b;
+                }
+            }
+        }
+        product
+    }
+
+    pub fn transpose(&self) -> BitMatrix {
+        let mut t = BitMatrix::zero(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in (0..self.cols).filter(|&c| self.get(r, c)) {
+                t.set(c, r, true);
+            }
+        }
+        t
+    }
+
+    // Matrix made of the first n rows
+    pub fn top_rows(&self, n: usize) -> BitMatrix {
+        Self {
+            rows: n,
+            cols: self.cols,
+            data: self.data[..n].to_vec(),
+        }
+    }
+
+    // Gauss-Jordan elimination to reduced row echelon form, returning the
+    // pivot columns (one per non-zero row, in order)
+    pub fn row_reduce(&mut self) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        for c in 0..self.cols {
+            let r = pivots.len();
+            if r == self.rows {
+                break;
+            }
+            let pivot = match (r..self.rows).find(|&i| self.get(i, c)) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            self.data.swap(r, pivot);
+            for i in 0..self.rows {
+                if i != r && self.get(i, c) {
+                    self.add_row(i, r);
+                }
+            }
+            pivots.push(c);
+        }
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().len()
+    }
+
+    // Basis of the kernel {x : self * x = 0}, as the rows of a matrix: one
+    // vector per free column, with the pivot variables solved for
+    pub fn kernel(&self) -> BitMatrix {
+        let mut reduced = self.clone();
+        let pivots = reduced.row_reduce();
+        let free: Vec<usize> = (0..self.cols).filter(|c| !pivots.contains(c)).collect();
+        let mut basis = BitMatrix::zero(free.len(), self.cols);
+        for (i, &f) in free.iter().enumerate() {
+            basis.set(i, f, true);
+            for (r, &p) in pivots.iter().enumerate() {
+                basis.set(i, p, reduced.get(r, f));
+            }
+        }
+        basis
+    }
+
+    // Random sum of rows, e.g. a random vector of a kernel
+    pub fn random_combination(&self) -> Vec<bool> {
+        let mut rng = rand::thread_rng();
+        let mut sum = vec![0_u64; self.cols.div_ceil(64)];
+        for row in self.data.iter().filter(|_| rng.gen()) {
+            for (a, b) in sum.iter_mut().zip(row) {
+                *a ^= b;
+            }
+        }
+        (0..self.cols)
+            .map(|c| (sum[c / 64] >> (c % 64)) & 1 == 1)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gf2_matrix() {
+        let mut m = BitMatrix::zero(3, 70);
+        for (r, c) in [(0, 0), (0, 69), (1, 1), (1, 69), (2, 0), (2, 1)] {
+            m.set(r, c, true);
+        }
+        // Row 2 is the sum of rows 0 & 1
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.transpose().transpose(), m);
+        assert_eq!(m.mul(&BitMatrix::identity(70)), m);
+
+        let kernel = m.kernel();
+        assert_eq!(kernel.rows(), 68);
+        assert!(m.mul(&kernel.transpose()).is_zero());
+        assert_eq!(kernel.rank(), 68);
+
+        let x = kernel.random_combination();
+        let mut column = BitMatrix::zero(70, 1);
+        for (i, &bit) in x.iter().enumerate() {
+            column.set(i, 0, bit);
+        }
+        assert!(m.mul(&column).is_zero());
+        assert!(m.add(&m).is_zero());
+    }
+}
@@ -7,7 +7,7 @@ const LETTER_FREQ: [f64; 27] = [
 ];
 
 pub fn calc_letter_freq_score(s: &str) -> f64 {
-    let mut counts = vec![0_u32; 27];
+    let mut counts = [0_u32; 27];
     let mut score: f64 = 0_f64;
 
     s.chars().for_each(|c| match c {
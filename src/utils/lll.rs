@@ -0,0 +1,135 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+pub type Vector = Vec<BigRational>;
+
+pub fn dot(u: &[BigRational], v: &[BigRational]) -> BigRational {
+    u.iter()
+        .zip(v)
+        .fold(BigRational::zero(), |acc, (a, b)| acc + a * b)
+}
+
+// Integer vector as a rational one
+pub fn to_rational(v: &[BigInt]) -> Vector {
+    v.iter()
+        .map(|x| BigRational::from_integer(x.clone()))
+        .collect()
+}
+
+// Lenstra-Lenstra-Lovasz reduction of linearly independent vectors, in
+// exact rational arithmetic. Gram-Schmidt coefficients mu & squared norms B
+// of the orthogonalised vectors are updated in place on size reductions &
+// swaps rather than recomputed (Cohen, Algorithm 2.6.3). None if the
+// vectors are linearly dependent.
+pub fn lll(basis: &[Vector], delta: &BigRational) -> Option<Vec<Vector>> {
+    let n = basis.len();
+    let mut b = basis.to_vec();
+    if n == 0 {
+        return Some(b);
+    }
+    let mut mu = vec![vec![BigRational::zero(); n]; n];
+    let mut norms = vec![BigRational::zero(); n];
+    norms[0] = dot(&b[0], &b[0]);
+    if norms[0].is_zero() {
+        return None;
+    }
+    let (mut k, mut k_max) = (1, 0);
+
+    while k < n {
+        // Gram-Schmidt of a vector not seen yet
+        if k > k_max {
+            k_max = k;
+            for j in 0..=k {
+                let u = (0..j).fold(dot(&b[k], &b[j]), |acc, i| {
+                    acc - &mu[j][i] * &mu[k][i] * &norms[i]
+                });
+                if j < k {
+                    mu[k][j] = u / &norms[j];
+                } else if u.is_zero() {
+                    return None;
+                } else {
+                    norms[k] = u;
+                }
+            }
+        }
+
+        reduce(&mut b, &mut mu, k, k - 1);
+        let mu_sq = &mu[k][k - 1] * &mu[k][k - 1];
+        if norms[k] < (delta - mu_sq) * &norms[k - 1] {
+            swap(&mut b, &mut mu, &mut norms, k, k_max);
+            k = 1.max(k - 1);
+        } else {
+            for l in (0..k - 1).rev() {
+                reduce(&mut b, &mut mu, k, l);
+            }
+            k += 1;
+        }
+    }
+    Some(b)
+}
+
+// Size reduction of b_k against b_l, making |mu_kl| <= 1/2
+fn reduce(b: &mut [Vector], mu: &mut [Vector], k: usize, l: usize) {
+    let half = BigRational::new(BigInt::one(), BigInt::from(2));
+    if mu[k][l].abs() <= half {
+        return;
+    }
+    let q = mu[k][l].round();
+    let b_l = b[l].clone();
+    for (x, y) in b[k].iter_mut().zip(&b_l) {
+        *x -= &q * y;
+    }
+    mu[k][l] -= &q;
+    let mu_l = mu[l].clone();
+    for (x, y) in mu[k].iter_mut().zip(&mu_l).take(l) {
+        *x -= &q * y;
+    }
+}
+
+// Swaps b_k & b_(k-1), updating mu & B
+fn swap(b: &mut [Vector], mu: &mut [Vector], norms: &mut [BigRational], k: usize, k_max: usize) {
+    b.swap(k, k - 1);
+    let (lower, upper) = mu.split_at_mut(k);
+    for (x, y) in lower[k - 1].iter_mut().zip(upper[0].iter_mut()).take(k - 1) {
+        std::mem::swap(x, y);
+    }
+    let m = mu[k][k - 1].clone();
+    let norm = &norms[k] + &m * &m * &norms[k - 1];
+    mu[k][k - 1] = &m * &norms[k - 1] / &norm;
+    norms[k] = &norms[k - 1] * &norms[k] / &norm;
+    norms[k - 1] = norm;
+    let new_m = mu[k][k - 1].clone();
+    for row in mu.iter_mut().take(k_max + 1).skip(k + 1) {
+        let t = row[k].clone();
+        row[k] = &row[k - 1] - &m * &t;
+        row[k - 1] = t + &new_m * &row[k];
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lll() {
+        let basis: Vec<Vector> = [[1, 1, 1], [-1, 0, 2], [3, 5, 6]]
+            .iter()
+            .map(|v| to_rational(&v.map(BigInt::from)))
+            .collect();
+        let expected: Vec<Vector> = [[0, 1, 0], [1, 0, 1], [-1, 0, 2]]
+            .iter()
+            .map(|v| to_rational(&v.map(BigInt::from)))
+            .collect();
+        let delta = BigRational::new(BigInt::from(3), BigInt::from(4));
+        assert_eq!(lll(&basis, &delta), Some(expected));
+
+        let dependent: Vec<Vector> = [[1, 1, 1], [2, 2, 2]]
+            .iter()
+            .map(|v| to_rational(&v.map(BigInt::from)))
+            .collect();
+        assert_eq!(lll(&dependent, &delta), None);
+        assert_eq!(lll(&dependent[..1], &delta), Some(dependent[..1].to_vec()));
+        assert_eq!(lll(&[to_rational(&[BigInt::zero()])], &delta), None);
+    }
+}
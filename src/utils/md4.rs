@@ -0,0 +1,119 @@
+pub const INIT_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+// Per step rotations of each round
+pub const R1_SHIFTS: [u32; 4] = [3, 7, 11, 19];
+pub const R2_SHIFTS: [u32; 4] = [3, 5, 9, 13];
+pub const R3_SHIFTS: [u32; 4] = [3, 9, 11, 15];
+
+// Order of message words used in each round
+pub const R2_ORDER: [usize; 16] = [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15];
+pub const R3_ORDER: [usize; 16] = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+
+pub fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+
+pub fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+
+pub fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+
+pub fn r1(a: u32, b: u32, c: u32, d: u32, x: u32, s: u32) -> u32 {
+    a.wrapping_add(f(b, c, d)).wrapping_add(x).rotate_left(s)
+}
+
+pub fn r2(a: u32, b: u32, c: u32, d: u32, x: u32, s: u32) -> u32 {
+    a.wrapping_add(g(b, c, d))
+        .wrapping_add(x)
+        .wrapping_add(0x5a827999)
+        .rotate_left(s)
+}
+
+pub fn r3(a: u32, b: u32, c: u32, d: u32, x: u32, s: u32) -> u32 {
+    a.wrapping_add(h(b, c, d))
+        .wrapping_add(x)
+        .wrapping_add(0x6ed9eba1)
+        .rotate_left(s)
+}
+
+// Inverse of r1 step, giving message word which yields `out`
+pub fn r1_inverse(out: u32, a: u32, b: u32, c: u32, d: u32, s: u32) -> u32 {
+    out.rotate_right(s).wrapping_sub(a).wrapping_sub(f(b, c, d))
+}
+
+// Intermediate values of all 48 steps, preceded by initial a, d, c, b.
+// So with i-th step of a round writing to a_i, d_i, c_i, b_i in turn, the
+// value of a_i is at 4i, d_i at 4i + 1, c_i at 4i + 2 & b_i at 4i + 3.
+pub fn step_values(state: &[u32; 4], x: &[u32; 16]) -> Vec<u32> {
+    let [a, b, c, d] = *state;
+    let mut v = vec![a, d, c, b];
+    for i in 0..48 {
+        let (prev_a, b, c, d) = (v[i], v[i + 3], v[i + 2], v[i + 1]);
+        let next = match i / 16 {
+            0 => r1(prev_a, b, c, d, x[i], R1_SHIFTS[i % 4]),
+            1 => r2(prev_a, b, c, d, x[R2_ORDER[i % 16]], R2_SHIFTS[i % 4]),
+            _ => r3(prev_a, b, c, d, x[R3_ORDER[i % 16]], R3_SHIFTS[i % 4]),
+        };
+        v.push(next);
+    }
+    v
+}
+
+pub fn compress(state: &[u32; 4], x: &[u32; 16]) -> [u32; 4] {
+    let v = step_values(state, x);
+    let n = v.len();
+    [
+        state[0].wrapping_add(v[n - 4]),
+        state[1].wrapping_add(v[n - 1]),
+        state[2].wrapping_add(v[n - 2]),
+        state[3].wrapping_add(v[n - 3]),
+    ]
+}
+
+pub fn block_to_words(block: &[u8]) -> [u32; 16] {
+    let mut x = [0_u32; 16];
+    for (i, word) in block.chunks(4).enumerate() {
+        x[i] = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    x
+}
+
+pub fn words_to_block(x: &[u32; 16]) -> Vec<u8> {
+    x.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+pub fn md4(msg: &[u8]) -> Vec<u8> {
+    let n_zeros = (64 - (msg.len() + 9) % 64) % 64;
+    let padded: Vec<u8> = msg
+        .iter()
+        .cloned()
+        .chain(std::iter::once(0x80))
+        .chain(std::iter::repeat_n(0, n_zeros))
+        .chain(((msg.len() as u64) * 8).to_le_bytes())
+        .collect();
+
+    let state = padded.chunks(64).fold(INIT_STATE, |state, block| {
+        compress(&state, &block_to_words(block))
+    });
+    state.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::md4;
+
+    #[test]
+    fn test_md4() {
+        assert_eq!(hex::encode(md4(b"")), "31d6cfe0d16ae931b73c59d7e0c089c0");
+        assert_eq!(hex::encode(md4(b"abc")), "a448017aaf21d8525fc10ae87aa6729d");
+        assert_eq!(
+            hex::encode(md4(
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"
+            )),
+            "e33b4ddc9c38f2199c3e7b164fcc0536"
+        );
+    }
+}
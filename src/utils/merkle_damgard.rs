@@ -0,0 +1,113 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::aes::aes128_encrypt_block;
+use std::cell::Cell;
+
+pub const BLOCK_SIZE: usize = 16;
+
+const IV: &[u8] = &[
+    0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10,
+];
+
+pub trait MerkleDamgard {
+    fn initial_state(&self) -> Vec<u8>;
+
+    fn compress(&self, state: &[u8], block: &[u8]) -> Result<Vec<u8>>;
+
+    // Runs compression over msg blocks (msg must be block aligned)
+    fn iterate(&self, state: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+        msg.chunks(BLOCK_SIZE)
+            .try_fold(state.to_vec(), |state, block| self.compress(&state, block))
+    }
+
+    fn hash(&self, msg: &[u8]) -> Result<Vec<u8>> {
+        self.iterate(&self.initial_state(), &md_pad(msg, msg.len()))
+    }
+}
+
+// Random message block, for the birthday searches of the attacks
+pub fn gen_rand_block() -> Vec<u8> {
+    (0..BLOCK_SIZE).map(|_| rand::random::<u8>()).collect()
+}
+
+// MD strengthening: 0x80, zeros & 64 bit big endian bit length of
+// whole message, so that the result is block aligned
+pub fn md_pad(msg: &[u8], total_len: usize) -> Vec<u8> {
+    let n_zeros = (BLOCK_SIZE - (msg.len() + 9) % BLOCK_SIZE) % BLOCK_SIZE;
+    msg.iter()
+        .cloned()
+        .chain(std::iter::once(0x80))
+        .chain(std::iter::repeat_n(0, n_zeros))
+        .chain(((total_len as u64) * 8).to_be_bytes())
+        .collect()
+}
+
+// Cheap hash: AES with state (zero padded) as key, truncated to state_len
+// bytes, as compression function
+pub struct AesMD {
+    pub state_len: usize,
+    calls: Cell<usize>,
+}
+
+impl AesMD {
+    // State is (part of) the AES key, so 1 to 16 bytes
+    pub fn new(state_len: usize) -> Result<Self> {
+        if state_len == 0 || state_len > 16 {
+            return Err(CryptoError::InvalidStateLength(state_len));
+        }
+        Ok(Self {
+            state_len,
+            calls: Cell::new(0),
+        })
+    }
+
+    // No. of compression function calls made so far
+    pub fn calls(&self) -> usize {
+        self.calls.get()
+    }
+}
+
+impl MerkleDamgard for AesMD {
+    fn initial_state(&self) -> Vec<u8> {
+        IV[..self.state_len].to_vec()
+    }
+
+    fn compress(&self, state: &[u8], block: &[u8]) -> Result<Vec<u8>> {
+        if state.len() != self.state_len {
+            return Err(CryptoError::InvalidStateLength(state.len()));
+        }
+        self.calls.set(self.calls.get() + 1);
+        let mut key = [0_u8; 16];
+        key[..state.len()].copy_from_slice(state);
+        let mut out = aes128_encrypt_block(block, &key)?;
+        out.truncate(self.state_len);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_aes_md_errors() {
+        assert_eq!(
+            AesMD::new(0).err(),
+            Some(CryptoError::InvalidStateLength(0))
+        );
+        assert_eq!(
+            AesMD::new(17).err(),
+            Some(CryptoError::InvalidStateLength(17))
+        );
+
+        let h = AesMD::new(2).unwrap();
+        assert_eq!(h.hash(b"hello").unwrap().len(), 2);
+        assert_eq!(
+            h.compress(&h.initial_state(), &[0; 15]),
+            Err(CryptoError::InvalidBlockLength(15))
+        );
+        assert_eq!(
+            h.compress(&[0; 3], &[0; 16]),
+            Err(CryptoError::InvalidStateLength(3))
+        );
+    }
+}
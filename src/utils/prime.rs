@@ -0,0 +1,59 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+const SMALL_PRIMES: [u32; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+// Miller-Rabin probabilistic primality test
+pub fn is_probable_prime(n: &BigUint, rounds: usize) -> bool {
+    if *n < BigUint::from(2_u8) {
+        return false;
+    }
+    for &sp in SMALL_PRIMES.iter() {
+        let sp = BigUint::from(sp);
+        if *n == sp {
+            return true;
+        }
+        if (n % &sp).is_zero() {
+            return false;
+        }
+    }
+
+    // n - 1 = 2^s * d with d odd
+    let one = BigUint::one();
+    let n_minus_one = n - &one;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> s;
+
+    let mut rng = rand::thread_rng();
+    'witness: for _ in 0..rounds {
+        let a = rng.gen_biguint_range(&BigUint::from(2_u8), &n_minus_one);
+        let mut x = a.modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+
+    true
+}
+
+// Random prime of exactly `bits` bits
+pub fn gen_prime(bits: u64) -> BigUint {
+    let mut rng = rand::thread_rng();
+    loop {
+        let mut candidate = rng.gen_biguint(bits);
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(0, true);
+        if is_probable_prime(&candidate, 40) {
+            return candidate;
+        }
+    }
+}
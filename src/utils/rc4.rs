@@ -0,0 +1,65 @@
+use crate::error::{CryptoError, Result};
+
+pub struct RC4 {
+    s: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl RC4 {
+    // Key scheduling
+    pub fn new(key: &[u8]) -> Result<Self> {
+        if key.is_empty() || key.len() > 256 {
+            return Err(CryptoError::InvalidKeyLength(key.len()));
+        }
+        let mut s = [0_u8; 256];
+        for (i, x) in s.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let mut j = 0_u8;
+        for (i, k) in (0..256).zip(key.iter().cycle()) {
+            j = j.wrapping_add(s[i]).wrapping_add(*k);
+            s.swap(i, j as usize);
+        }
+        Ok(Self { s, i: 0, j: 0 })
+    }
+
+    pub fn next_byte(&mut self) -> u8 {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.s[self.i as usize]);
+        self.s.swap(self.i as usize, self.j as usize);
+        self.s[self.s[self.i as usize].wrapping_add(self.s[self.j as usize]) as usize]
+    }
+
+    pub fn keystream(&mut self, n: usize) -> Vec<u8> {
+        (0..n).map(|_| self.next_byte()).collect()
+    }
+
+    // Encryption & decryption alike
+    pub fn apply(&mut self, data: &[u8]) -> Vec<u8> {
+        data.iter().map(|b| b ^ self.next_byte()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rc4() {
+        assert_eq!(
+            hex::encode(RC4::new(b"Key").unwrap().apply(b"Plaintext")),
+            "bbf316e8d940af0ad3"
+        );
+        assert_eq!(
+            hex::encode(RC4::new(b"Wiki").unwrap().apply(b"pedia")),
+            "1021bf0420"
+        );
+        assert_eq!(
+            hex::encode(RC4::new(b"Secret").unwrap().apply(b"Attack at dawn")),
+            "45a01f645fc35b383552544b9bf5"
+        );
+
+        assert_eq!(RC4::new(&[]).err(), Some(CryptoError::InvalidKeyLength(0)));
+    }
+}
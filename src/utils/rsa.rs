@@ -0,0 +1,111 @@
+use crate::error::{CryptoError, Result};
+use crate::utils::prime::gen_prime;
+use crate::utils::sha::sha1_hash;
+use num_bigint::BigUint;
+use rand::Rng;
+
+// ASN.1 DigestInfo prefix of a SHA-1 digest
+const SHA1_DIGEST_INFO: &[u8] = &[
+    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+];
+
+pub struct RSA {
+    pub e: BigUint,
+    pub n: BigUint,
+    d: BigUint,
+}
+
+impl RSA {
+    // Generates a key pair with modulus of `bits` bits and e = 3
+    pub fn new(bits: u64) -> Self {
+        let e = BigUint::from(3_u8);
+        loop {
+            let p = gen_prime(bits / 2);
+            let q = gen_prime(bits - bits / 2);
+            let n = &p * &q;
+            if p == q || n.bits() != bits {
+                continue;
+            }
+            let et = (p - 1_u8) * (q - 1_u8);
+            if let Some(d) = e.modinv(&et) {
+                return Self { e, n, d };
+            }
+        }
+    }
+
+    pub fn from_parts(e: &BigUint, n: &BigUint, d: &BigUint) -> Self {
+        Self {
+            e: e.clone(),
+            n: n.clone(),
+            d: d.clone(),
+        }
+    }
+
+    pub fn encrypt(&self, m: &BigUint) -> BigUint {
+        m.modpow(&self.e, &self.n)
+    }
+
+    pub fn decrypt(&self, c: &BigUint) -> BigUint {
+        c.modpow(&self.d, &self.n)
+    }
+
+    // Byte length of the modulus
+    pub fn k(&self) -> usize {
+        self.n.bits().div_ceil(8) as usize
+    }
+
+    // PKCS#1 v1.5 signature of SHA-1 of msg
+    pub fn sign(&self, msg: &[u8]) -> Result<BigUint> {
+        let block = pad_pkcs1_v15_sign(&sha1_hash(msg), self.k())?;
+        Ok(self.decrypt(&BigUint::from_bytes_be(&block)))
+    }
+
+    // Modulus too small for the signature block verifies nothing
+    pub fn verify(&self, msg: &[u8], sig: &BigUint) -> bool {
+        pad_pkcs1_v15_sign(&sha1_hash(msg), self.k())
+            .is_ok_and(|block| self.encrypt(sig) == BigUint::from_bytes_be(&block))
+    }
+}
+
+// PKCS#1 v1.5 signature block: 00 || 01 || FF...FF || 00 || DigestInfo ||
+// digest
+pub fn pad_pkcs1_v15_sign(digest: &[u8], k: usize) -> Result<Vec<u8>> {
+    let t = [SHA1_DIGEST_INFO, digest].concat();
+    if t.len() + 11 > k {
+        return Err(CryptoError::MessageTooLong(t.len()));
+    }
+    Ok([0_u8, 1]
+        .iter()
+        .cloned()
+        .chain(std::iter::repeat_n(0xff, k - 3 - t.len()))
+        .chain(std::iter::once(0))
+        .chain(t)
+        .collect())
+}
+
+// PKCS#1 v1.5 encryption block: 00 || 02 || PS || 00 || msg, where PS is
+// at least 8 random non-zero bytes
+pub fn pad_pkcs1_v15(msg: &[u8], k: usize) -> Result<Vec<u8>> {
+    if msg.len() + 11 > k {
+        return Err(CryptoError::MessageTooLong(msg.len()));
+    }
+    let ps = (0..k - 3 - msg.len()).map(|_| rand::thread_rng().gen_range(1..=255_u8));
+    Ok([0_u8, 2]
+        .iter()
+        .cloned()
+        .chain(ps)
+        .chain(std::iter::once(0))
+        .chain(msg.iter().cloned())
+        .collect())
+}
+
+pub fn unpad_pkcs1_v15(block: &[u8]) -> Option<Vec<u8>> {
+    if block.len() < 11 || block[0] != 0 || block[1] != 2 {
+        return None;
+    }
+    let sep = block.iter().skip(2).position(|&b| b == 0)? + 2;
+    if sep < 10 {
+        return None;
+    }
+    Some(block[sep + 1..].to_vec())
+}
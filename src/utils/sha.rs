@@ -4,5 +4,36 @@ pub fn sha1_hash(bytes: &[u8]) -> Vec<u8> {
     let mut hasher = Sha1::new();
     hasher.update(bytes);
     let result = hasher.finalize();
-    result.iter().map(|x| *x).collect()
+    result.iter().copied().collect()
+}
+
+pub fn hmac_sha1(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+    let mut key = if key.len() > BLOCK_SIZE {
+        sha1_hash(key)
+    } else {
+        key.to_vec()
+    };
+    key.resize(BLOCK_SIZE, 0);
+
+    let inner_key: Vec<u8> = key.iter().map(|b| b ^ 0x36).collect();
+    let outer_key: Vec<u8> = key.iter().map(|b| b ^ 0x5c).collect();
+    let inner = sha1_hash(&[&inner_key, msg].concat());
+    sha1_hash(&[outer_key, inner].concat())
+}
+
+#[cfg(test)]
+mod test {
+    use super::hmac_sha1;
+
+    #[test]
+    fn test_hmac_sha1() {
+        assert_eq!(
+            hex::encode(hmac_sha1(
+                b"key",
+                b"The quick brown fox jumps over the lazy dog"
+            )),
+            "de7c9b85b8b78aa6bc8a7a36f70a90701c9db4d9"
+        );
+    }
 }
@@ -0,0 +1,177 @@
+use num_bigint::BigUint;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const KEY_HEX: &str = "59454c4c4f57205355424d4152494e45"; // YELLOW SUBMARINE
+
+fn cryptopals(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cryptopals"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Error running cryptopals!");
+    // Commands failing early may not read stdin at all
+    let _ = child.stdin.take().unwrap().write_all(stdin);
+    child.wait_with_output().unwrap()
+}
+
+fn stdout_of(args: &[&str], stdin: &[u8]) -> Vec<u8> {
+    let output = cryptopals(args, stdin);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output.stdout
+}
+
+#[test]
+fn test_cli_xor_break() {
+    let key = stdout_of(
+        &["xor-break", "--in", "base64", "files/set_1/6_base64.txt"],
+        b"",
+    );
+    assert_eq!(key, b"Terminator X: Bring the noise");
+
+    let key_hex = stdout_of(
+        &[
+            "xor-break",
+            "--in",
+            "base64",
+            "--out",
+            "hex",
+            "files/set_1/6_base64.txt",
+        ],
+        b"",
+    );
+    assert_eq!(key_hex, format!("{}\n", hex::encode(&key)).as_bytes());
+}
+
+#[test]
+fn test_cli_single_xor() {
+    let message = stdout_of(
+        &[
+            "single-xor",
+            "--lines",
+            "--in",
+            "hex",
+            "files/set_1/4_strings.txt",
+        ],
+        b"",
+    );
+    assert_eq!(message, b"Now that the party is jumping\n");
+
+    let hex = b"1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
+    assert_eq!(
+        stdout_of(&["single-xor", "--in", "hex"], hex),
+        b"Cooking MC's like a pound of bacon"
+    );
+}
+
+#[test]
+fn test_cli_detect_ecb() {
+    let output = stdout_of(&["detect-ecb", "--in", "hex", "files/set_1/8_hex.txt"], b"");
+    assert!(String::from_utf8(output).unwrap().starts_with("line 132 "));
+}
+
+#[test]
+fn test_cli_aes() {
+    let message = stdout_of(
+        &[
+            "aes",
+            "ecb",
+            "decrypt",
+            "--key",
+            KEY_HEX,
+            "--in",
+            "base64",
+            "files/set_1/7_base64.txt",
+        ],
+        b"",
+    );
+    assert!(message.starts_with(b"I'm back and I'm ringin' the bell \n"));
+    assert!(message.ends_with(b"Play that funky music \n"));
+
+    // Binary data round trips through base64 on stdin/stdout
+    let plain: Vec<u8> = (0..=255).collect();
+    let iv = "000102030405060708090a0b0c0d0e0f";
+    let cipher = stdout_of(
+        &[
+            "aes", "cbc", "encrypt", "--key", KEY_HEX, "--iv", iv, "--out", "base64",
+        ],
+        &plain,
+    );
+    let decrypted = stdout_of(
+        &[
+            "aes", "cbc", "decrypt", "--key", KEY_HEX, "--iv", iv, "--in", "base64",
+        ],
+        &cipher,
+    );
+    assert_eq!(decrypted, plain);
+
+    let output = cryptopals(&["aes", "cbc", "decrypt", "--key", "59454c4c"], &[0; 16]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid key length: 4"));
+}
+
+#[test]
+fn test_cli_pad() {
+    let padded = stdout_of(
+        &["pad", "--block", "20", "--out", "hex"],
+        b"YELLOW SUBMARINE",
+    );
+    assert_eq!(padded, b"59454c4c4f57205355424d4152494e4504040404\n");
+
+    let unpadded = stdout_of(&["unpad", "--block", "20", "--in", "hex"], &padded);
+    assert_eq!(unpadded, b"YELLOW SUBMARINE");
+
+    let output = cryptopals(&["unpad"], b"ICE ICE BABY\x01\x02\x03\x04");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("bad padding"));
+}
+
+#[test]
+fn test_cli_pad_block_range() {
+    // Block size 0 used to divide by zero, and 300 to truncate the padding
+    // size in the padding bytes
+    for block in ["0", "256", "300"] {
+        for command in ["pad", "unpad"] {
+            let output = cryptopals(&[command, "--block", block], b"YELLOW SUBMARINE");
+            assert_eq!(output.status.code(), Some(1));
+            assert!(String::from_utf8_lossy(&output.stderr)
+                .contains("--block must be between 1 and 255"));
+        }
+    }
+
+    let padded = stdout_of(&["pad", "--block", "255"], b"YELLOW SUBMARINE");
+    assert_eq!(padded.len(), 255);
+    assert!(padded.ends_with(&[0xef; 239]));
+}
+
+#[test]
+fn test_cli_dh_keygen() {
+    let output = stdout_of(&["dh", "keygen", "--p", "25", "--g", "05"], b"");
+    let keys: Vec<BigUint> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| BigUint::from_bytes_be(&hex::decode(line).unwrap()))
+        .collect();
+    assert_eq!(keys.len(), 2);
+    let p = BigUint::from(37u8);
+    assert_eq!(BigUint::from(5u8).modpow(&keys[0], &p), keys[1]);
+
+    // Default group is the NIST prime from c33
+    let output = stdout_of(&["dh", "keygen"], b"");
+    assert_eq!(String::from_utf8(output).unwrap().lines().count(), 2);
+}
+
+#[test]
+fn test_cli_errors() {
+    assert!(!cryptopals(&["frobnicate"], b"").status.success());
+    assert!(!cryptopals(&["single-xor", "--in", "hex"], b"xyz")
+        .status
+        .success());
+    assert!(!cryptopals(&["aes", "ctr", "encrypt"], b"").status.success());
+}
@@ -0,0 +1,68 @@
+use cryptopals_crypto_challenges::registry;
+use std::process::{Command, Output};
+
+fn runner(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_runner"))
+        .args(args)
+        .output()
+        .expect("Error running runner!")
+}
+
+// Value in the named column of challenge number's row. Columns are right
+// aligned to the end of their header, except for the last one.
+fn column<'a>(report: &'a str, number: &str, name: &str) -> &'a str {
+    let header = report.lines().next().unwrap();
+    let end = header.find(name).unwrap() + name.len();
+    let row = report
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some(number))
+        .unwrap();
+    row[..end].split_whitespace().last().unwrap()
+}
+
+#[test]
+fn test_runner_report() {
+    let output = runner(&["6", "--set", "2", "3", "6"]);
+    assert!(output.status.success());
+    let report = String::from_utf8(output.stdout).unwrap();
+
+    // Header, challenges 3, 6 & set 2 in order, then the summary
+    let numbers: Vec<&str> = report
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .map(|line| line.split_whitespace().next().unwrap())
+        .collect();
+    assert_eq!(
+        numbers,
+        ["3", "6", "9", "10", "11", "12", "13", "14", "15", "16"]
+    );
+    assert!(report.contains("Terminator X: Bring the noise"));
+    assert!(report.contains("YELLOW SUBMARINE\\u{4}\\u{4}\\u{4}\\u{4}"));
+    assert_eq!(column(&report, "3", "Queries"), "-");
+    assert_eq!(column(&report, "11", "Queries"), "100");
+    for number in [12, 13, 14, 16] {
+        let queries = registry::find(number).unwrap().run().unwrap().queries;
+        assert_eq!(
+            column(&report, &number.to_string(), "Queries"),
+            queries.unwrap().to_string()
+        );
+    }
+    assert!(report.ends_with("10 of 10 challenges solved\n"));
+}
+
+#[test]
+fn test_runner_list() {
+    let output = runner(&["--list", "--set", "8"]);
+    assert!(output.status.success());
+    let listing = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(listing.lines().count(), 8);
+    assert!(listing.contains("Pollard's Method for Catching Kangaroos"));
+}
+
+#[test]
+fn test_runner_errors() {
+    assert_eq!(runner(&["17"]).status.code(), Some(2));
+    assert_eq!(runner(&["--set", "3"]).status.code(), Some(2));
+    assert_eq!(runner(&["--set"]).status.code(), Some(2));
+}